@@ -0,0 +1,63 @@
+use super::*;
+use std::sync::Mutex;
+
+pub trait FastCacheBackend<T> {
+    fn map_backend<U, M>(self, map: M) -> U
+    where
+        M: FnOnce(&mut Option<T>) -> U;
+}
+
+impl<T> FastCacheBackend<T> for &mut Option<T> {
+    fn map_backend<U, M>(self, map: M) -> U
+    where
+        M: FnOnce(&mut Option<T>) -> U,
+    {
+        map(self)
+    }
+}
+
+impl<T> FastCacheBackend<T> for &RefCell<Option<T>> {
+    fn map_backend<U, M>(self, map: M) -> U
+    where
+        M: FnOnce(&mut Option<T>) -> U,
+    {
+        map(&mut self.borrow_mut())
+    }
+}
+
+impl<T> FastCacheBackend<T> for &Mutex<Option<T>> {
+    fn map_backend<U, M>(self, map: M) -> U
+    where
+        M: FnOnce(&mut Option<T>) -> U,
+    {
+        map(&mut self.lock().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cached_len<B>(backend: B, source: &str) -> usize
+    where
+        B: FastCacheBackend<usize>,
+    {
+        backend.map_backend(|cache| *cache.access(|_| false, || source.len()).take())
+    }
+
+    #[test]
+    fn one_generic_helper_accepts_every_backend() {
+        let mut owned = None;
+        let shared = RefCell::new(None);
+        let locked = Mutex::new(None);
+
+        assert_eq!(cached_len(&mut owned, "abc"), 3);
+        assert_eq!(cached_len(&shared, "abcd"), 4);
+        assert_eq!(cached_len(&locked, "ab"), 2);
+
+        assert_eq!(cached_len(&mut owned, "changed"), 3);
+        assert_eq!(cached_len(&shared, "changed"), 4);
+        assert_eq!(cached_len(&locked, "changed"), 2);
+        assert_eq!(*shared.borrow(), Some(4));
+    }
+}
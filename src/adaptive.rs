@@ -0,0 +1,154 @@
+use super::*;
+use std::time::Duration;
+
+pub struct CostStamped<T> {
+    pub value: T,
+    pub tick: u32,
+    pub cost: Duration,
+}
+
+impl<T> CostStamped<T> {
+    pub fn new(value: T, tick: u32, cost: Duration) -> Self {
+        CostStamped { value, tick, cost }
+    }
+
+    pub fn age(&self, current_tick: u32) -> u32 {
+        current_tick.wrapping_sub(self.tick)
+    }
+}
+
+pub struct AdaptiveTtl<M, P>
+where
+    M: Fn() -> Duration,
+    P: Fn(Duration) -> u32,
+{
+    measure: M,
+    ttl_for_cost: P,
+}
+
+impl<M, P> AdaptiveTtl<M, P>
+where
+    M: Fn() -> Duration,
+    P: Fn(Duration) -> u32,
+{
+    pub fn new(measure: M, ttl_for_cost: P) -> Self {
+        AdaptiveTtl {
+            measure,
+            ttl_for_cost,
+        }
+    }
+
+    pub fn ttl_for(&self, cost: Duration) -> u32 {
+        (self.ttl_for_cost)(cost)
+    }
+
+    pub fn is_expired<T>(&self, value: &CostStamped<T>, current_tick: u32) -> bool {
+        value.age(current_tick) >= self.ttl_for(value.cost)
+    }
+
+    pub fn fill<T, F>(&self, current_tick: u32, filler: F) -> CostStamped<T>
+    where
+        F: FnOnce() -> T,
+    {
+        let start = (self.measure)();
+        let value = filler();
+        let cost = (self.measure)().saturating_sub(start);
+
+        CostStamped::new(value, current_tick, cost)
+    }
+}
+
+pub trait FastCacheAdaptiveAccessor<'a, T, R>: FastCacheAccessor<'a, CostStamped<T>, R> {
+    #[allow(clippy::type_complexity)]
+    fn access_adaptive<'p, M, P, F>(
+        self,
+        policy: &'p AdaptiveTtl<M, P>,
+        current_tick: u32,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        CostStamped<T>,
+        Self,
+        impl FnOnce(&CostStamped<T>) -> bool + 'p,
+        impl FnOnce() -> CostStamped<T> + 'p,
+        R,
+    >
+    where
+        M: Fn() -> Duration,
+        P: Fn(Duration) -> u32,
+        F: FnOnce() -> T + 'p;
+}
+
+impl<'a, C, T, R> FastCacheAdaptiveAccessor<'a, T, R> for C
+where
+    C: FastCacheAccessor<'a, CostStamped<T>, R>,
+{
+    #[allow(clippy::type_complexity)]
+    fn access_adaptive<'p, M, P, F>(
+        self,
+        policy: &'p AdaptiveTtl<M, P>,
+        current_tick: u32,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        CostStamped<T>,
+        Self,
+        impl FnOnce(&CostStamped<T>) -> bool + 'p,
+        impl FnOnce() -> CostStamped<T> + 'p,
+        R,
+    >
+    where
+        M: Fn() -> Duration,
+        P: Fn(Duration) -> u32,
+        F: FnOnce() -> T + 'p,
+    {
+        self.access(
+            move |v: &CostStamped<T>| policy.is_expired(v, current_tick),
+            move || policy.fill(current_tick, filler),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expensive_values_live_longer() {
+        let clock = Cell::new(Duration::ZERO);
+        let policy = AdaptiveTtl::new(|| clock.get(), |cost| cost.as_millis() as u32 / 10 + 1);
+
+        let work = |cost_ms: u64, value: u32| {
+            let clock = &clock;
+
+            move || {
+                clock.set(clock.get() + Duration::from_millis(cost_ms));
+                value
+            }
+        };
+
+        let mut cheap = None;
+        let mut expensive = None;
+
+        for tick in 0..4 {
+            (&mut cheap)
+                .access_adaptive(&policy, tick, work(5, tick))
+                .get();
+            (&mut expensive)
+                .access_adaptive(&policy, tick, work(30, tick))
+                .get();
+        }
+
+        let cheap = cheap.unwrap();
+        let expensive = expensive.unwrap();
+
+        assert_eq!(cheap.cost, Duration::from_millis(5));
+        assert_eq!(expensive.cost, Duration::from_millis(30));
+        assert_eq!(cheap.value, 3);
+        assert_eq!(expensive.value, 0);
+
+        assert!(policy.is_expired(&cheap, 4));
+        assert!(!policy.is_expired(&expensive, 3));
+        assert!(policy.is_expired(&expensive, 4));
+    }
+}
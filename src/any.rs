@@ -0,0 +1,127 @@
+use super::*;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+pub struct AnyCache<K = TypeId> {
+    entries: RefCell<HashMap<K, Box<dyn Any>>>,
+}
+
+impl<K> Default for AnyCache<K> {
+    fn default() -> Self {
+        AnyCache {
+            entries: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+impl<K> AnyCache<K>
+where
+    K: Hash + Eq,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.borrow().is_empty()
+    }
+
+    pub fn remove(&self, key: &K) -> bool {
+        self.entries.borrow_mut().remove(key).is_some()
+    }
+
+    pub fn clear(&self) {
+        self.entries.borrow_mut().clear();
+    }
+
+    pub fn get<T>(&self, key: &K) -> Result<Option<Ref<'_, T>>, CacheError>
+    where
+        T: Any,
+    {
+        let entries = self.entries.borrow();
+
+        match entries.get(key) {
+            Some(value) if !value.is::<T>() => Err(CacheError::TypeMismatch),
+            Some(_) => Ok(Some(Ref::map(entries, |e| {
+                e[key].downcast_ref::<T>().unwrap()
+            }))),
+            None => Ok(None),
+        }
+    }
+
+    pub fn get_or_insert_with<T, F>(&self, key: K, filler: F) -> Result<Ref<'_, T>, CacheError>
+    where
+        K: Clone,
+        T: Any,
+        F: FnOnce() -> T,
+    {
+        if !self.entries.borrow().contains_key(&key) {
+            let value = filler();
+
+            self.entries
+                .borrow_mut()
+                .insert(key.clone(), Box::new(value));
+        }
+
+        self.get(&key).map(|v| v.unwrap())
+    }
+}
+
+impl AnyCache<TypeId> {
+    pub fn get_or_insert_type_with<T, F>(&self, filler: F) -> Ref<'_, T>
+    where
+        T: Any,
+        F: FnOnce() -> T,
+    {
+        self.get_or_insert_with(TypeId::of::<T>(), filler)
+            .unwrap_or_else(|_| unreachable!())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stores_and_retrieves_values_of_different_types() {
+        let cache = AnyCache::new();
+
+        assert_eq!(*cache.get_or_insert_type_with(|| 7u32), 7);
+        assert_eq!(
+            *cache.get_or_insert_type_with(|| String::from("spawn")),
+            "spawn"
+        );
+        assert_eq!(cache.len(), 2);
+
+        assert_eq!(
+            *cache.get_or_insert_type_with::<u32, _>(|| unreachable!()),
+            7
+        );
+        assert_eq!(
+            *cache.get_or_insert_type_with::<String, _>(|| unreachable!()),
+            "spawn"
+        );
+    }
+
+    #[test]
+    fn reports_a_type_mismatch_for_a_key() {
+        let cache = AnyCache::new();
+
+        assert_eq!(*cache.get_or_insert_with("room", || 1u8).unwrap(), 1);
+
+        assert_eq!(
+            cache.get_or_insert_with("room", || 2u16).err(),
+            Some(CacheError::TypeMismatch)
+        );
+        assert_eq!(
+            cache.get::<u16>(&"room").err(),
+            Some(CacheError::TypeMismatch)
+        );
+        assert!(cache.get::<u8>(&"missing").unwrap().is_none());
+    }
+}
@@ -0,0 +1,123 @@
+use super::*;
+use std::sync::{Arc, Mutex};
+
+//
+// Mutex
+//
+
+impl<T> FastCacheExpiration<T> for &Mutex<Option<T>> {
+    fn expire_with<X>(self, expiration: X) -> Self
+    where
+        X: FnOnce(&T) -> bool,
+    {
+        (&mut *self.lock().unwrap()).expire_with(expiration);
+
+        self
+    }
+}
+
+impl<'a, T> FastCacheGet<'a, Arc<T>, Arc<T>> for &'a Mutex<Option<Arc<T>>> {
+    fn get_or_insert_with<F: FnOnce() -> Arc<T>>(self, f: F) -> Arc<T> {
+        self.lock().unwrap().get_or_insert_with(f).clone()
+    }
+}
+
+impl<'a, T> FastCacheMaybeGet<'a, Arc<T>, Arc<T>> for &'a Mutex<Option<Arc<T>>> {
+    fn maybe_get_or_insert_with<F: FnOnce() -> Option<Arc<T>>>(self, f: F) -> Option<Arc<T>> {
+        let mut data = self.lock().unwrap();
+
+        if data.is_none() {
+            *data = (f)();
+        }
+
+        data.clone()
+    }
+}
+
+impl<'a, T> FastCacheMaybeRetain<'a, Arc<T>, Arc<T>> for &'a Mutex<Option<Arc<T>>> {
+    fn maybe_get_or_retain<F: FnOnce() -> Option<Arc<T>>>(self, f: F) -> Result<Arc<T>, Self> {
+        self.maybe_get_or_insert_with(f).ok_or(self)
+    }
+}
+
+pub trait ArcHandle<T> {
+    fn to_arc(&self) -> Arc<T>;
+}
+
+impl<T> ArcHandle<T> for Arc<T> {
+    fn to_arc(&self) -> Arc<T> {
+        self.clone()
+    }
+}
+
+impl<T> ArcHandle<T> for &Arc<T> {
+    fn to_arc(&self) -> Arc<T> {
+        Arc::clone(self)
+    }
+}
+
+impl<T> ArcHandle<T> for Ref<'_, Arc<T>> {
+    fn to_arc(&self) -> Arc<T> {
+        Arc::clone(self)
+    }
+}
+
+impl<'c, U, C, X, F, R> CacheAccesor<'c, Arc<U>, C, X, F, R>
+where
+    F: FnOnce() -> Arc<U>,
+    X: FnOnce(&Arc<U>) -> bool,
+    C: FastCacheGet<'c, Arc<U>, R> + FastCacheExpiration<Arc<U>>,
+    R: ArcHandle<U>,
+{
+    pub fn get_arc(self) -> Arc<U> {
+        self.take().to_arc()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_arc_shares_the_cached_value() {
+        let cache: Mutex<Option<Arc<Vec<u32>>>> = Mutex::new(None);
+
+        let first = (&cache)
+            .access(|_| false, || Arc::new(vec![1, 2]))
+            .get_arc();
+        assert_eq!(Arc::strong_count(&first), 2);
+
+        let second = (&cache).access(|_| false, || unreachable!()).get_arc();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(Arc::strong_count(&first), 3);
+
+        *cache.lock().unwrap() = None;
+        assert_eq!(Arc::strong_count(&first), 2);
+        assert_eq!(*second, vec![1, 2]);
+    }
+
+    #[test]
+    fn get_arc_on_option_and_refcell_backends() {
+        let mut owned = None;
+        let value = (&mut owned).access(|_| false, || Arc::new(5)).get_arc();
+        assert_eq!(Arc::strong_count(&value), 2);
+
+        let shared = RefCell::new(None);
+        let value = (&shared).access(|_| false, || Arc::new(6)).get_arc();
+        assert_eq!(*value, 6);
+        assert_eq!(Arc::strong_count(&value), 2);
+    }
+
+    #[test]
+    fn mutex_backends_expire_and_refill() {
+        let cache = Mutex::new(Some(Arc::new(1)));
+
+        let value = (&cache).access(|v| **v == 1, || Arc::new(2)).get_arc();
+        assert_eq!(*value, 2);
+
+        let missing = (&cache)
+            .expire_with(|_| true)
+            .maybe_get_or_insert_with(|| None);
+        assert!(missing.is_none());
+    }
+}
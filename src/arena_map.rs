@@ -0,0 +1,246 @@
+use super::*;
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ArenaHandle {
+    index: usize,
+    generation: u32,
+}
+
+struct ArenaSlot<V> {
+    generation: u32,
+    value: Option<V>,
+}
+
+struct Arena<K, V> {
+    index: HashMap<K, ArenaHandle>,
+    slots: Vec<ArenaSlot<V>>,
+    free: Vec<usize>,
+}
+
+pub struct ArenaCacheMap<K, V> {
+    arena: RefCell<Arena<K, V>>,
+}
+
+impl<K, V> Default for ArenaCacheMap<K, V> {
+    fn default() -> Self {
+        ArenaCacheMap {
+            arena: RefCell::new(Arena {
+                index: HashMap::new(),
+                slots: Vec::new(),
+                free: Vec::new(),
+            }),
+        }
+    }
+}
+
+impl<K, V> ArenaCacheMap<K, V>
+where
+    K: Hash + Eq,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.arena.borrow().index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.arena.borrow().index.is_empty()
+    }
+
+    pub fn slot_count(&self) -> usize {
+        self.arena.borrow().slots.len()
+    }
+
+    pub fn free_count(&self) -> usize {
+        self.arena.borrow().free.len()
+    }
+
+    pub fn handle<Q>(&self, key: &Q) -> Option<ArenaHandle>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.arena.borrow().index.get(key).copied()
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<Ref<'_, V>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        Ref::filter_map(self.arena.borrow(), |a| {
+            let handle = a.index.get(key)?;
+
+            a.slots[handle.index].value.as_ref()
+        })
+        .ok()
+    }
+
+    pub fn get_by_handle(&self, handle: ArenaHandle) -> Option<Ref<'_, V>> {
+        Ref::filter_map(self.arena.borrow(), |a| {
+            a.slots
+                .get(handle.index)
+                .filter(|s| s.generation == handle.generation)
+                .and_then(|s| s.value.as_ref())
+        })
+        .ok()
+    }
+
+    pub fn insert(&self, key: K, value: V) -> (ArenaHandle, Option<V>) {
+        let mut arena = self.arena.borrow_mut();
+
+        if let Some(&handle) = arena.index.get(&key) {
+            let previous = arena.slots[handle.index].value.replace(value);
+
+            return (handle, previous);
+        }
+
+        let handle = match arena.free.pop() {
+            Some(index) => {
+                let slot = &mut arena.slots[index];
+
+                slot.value = Some(value);
+
+                ArenaHandle {
+                    index,
+                    generation: slot.generation,
+                }
+            }
+            None => {
+                arena.slots.push(ArenaSlot {
+                    generation: 0,
+                    value: Some(value),
+                });
+
+                ArenaHandle {
+                    index: arena.slots.len() - 1,
+                    generation: 0,
+                }
+            }
+        };
+
+        arena.index.insert(key, handle);
+
+        (handle, None)
+    }
+
+    pub fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let mut arena = self.arena.borrow_mut();
+
+        let handle = arena.index.remove(key)?;
+        let slot = &mut arena.slots[handle.index];
+
+        // Bumping the generation invalidates handles to the evicted value
+        // before the slot is reused.
+        slot.generation = slot.generation.wrapping_add(1);
+
+        let value = slot.value.take();
+
+        arena.free.push(handle.index);
+
+        value
+    }
+
+    pub fn clear(&self) {
+        let mut arena = self.arena.borrow_mut();
+        let arena = &mut *arena;
+
+        arena.index.clear();
+        arena.free.clear();
+
+        for (index, slot) in arena.slots.iter_mut().enumerate() {
+            if slot.value.take().is_some() {
+                slot.generation = slot.generation.wrapping_add(1);
+            }
+
+            arena.free.push(index);
+        }
+    }
+
+    pub fn get_or_insert_with<F>(&self, key: K, filler: F) -> Ref<'_, V>
+    where
+        F: FnOnce() -> V,
+    {
+        let handle = match self.handle(&key) {
+            Some(handle) => handle,
+            None => {
+                let value = filler();
+
+                self.insert(key, value).0
+            }
+        };
+
+        Ref::map(self.arena.borrow(), |a| {
+            a.slots[handle.index].value.as_ref().unwrap()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicted_slots_are_reused_with_a_new_generation() {
+        let map = ArenaCacheMap::new();
+
+        let (a, _) = map.insert("a", 1);
+        let (b, _) = map.insert("b", 2);
+        assert_eq!(map.slot_count(), 2);
+
+        assert_eq!(map.remove("a"), Some(1));
+        assert_eq!(map.free_count(), 1);
+        assert!(map.get_by_handle(a).is_none());
+
+        let (c, _) = map.insert("c", 3);
+        assert_eq!(map.slot_count(), 2);
+        assert_eq!(map.free_count(), 0);
+        assert_ne!(c, a);
+
+        assert!(map.get_by_handle(a).is_none());
+        assert_eq!(*map.get_by_handle(c).unwrap(), 3);
+        assert_eq!(*map.get_by_handle(b).unwrap(), 2);
+    }
+
+    #[test]
+    fn handles_are_stable_across_overwrites() {
+        let map = ArenaCacheMap::new();
+
+        let (handle, previous) = map.insert(1, "first");
+        assert_eq!(previous, None);
+
+        let (again, previous) = map.insert(1, "second");
+        assert_eq!(again, handle);
+        assert_eq!(previous, Some("first"));
+        assert_eq!(map.handle(&1), Some(handle));
+        assert_eq!(*map.get(&1).unwrap(), "second");
+    }
+
+    #[test]
+    fn clear_frees_every_slot() {
+        let map = ArenaCacheMap::new();
+
+        let (handle, _) = map.insert(1, 10);
+        assert_eq!(*map.get_or_insert_with(2, || 20), 20);
+        assert_eq!(*map.get_or_insert_with(2, || 30), 20);
+
+        map.clear();
+
+        assert!(map.is_empty());
+        assert_eq!(map.free_count(), 2);
+        assert!(map.get_by_handle(handle).is_none());
+
+        map.insert(3, 30);
+        assert_eq!(map.slot_count(), 2);
+        assert_eq!(map.len(), 1);
+    }
+}
@@ -0,0 +1,120 @@
+use super::*;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BenchReport {
+    pub accesses: usize,
+    pub fills: usize,
+    pub fill_time: Duration,
+    pub total_time: Duration,
+}
+
+impl BenchReport {
+    pub fn hits(&self) -> usize {
+        self.accesses - self.fills
+    }
+
+    pub fn hit_rate(&self) -> f64 {
+        if self.accesses == 0 {
+            0.0
+        } else {
+            self.hits() as f64 / self.accesses as f64
+        }
+    }
+}
+
+pub fn drive_ticks<T, X, F>(ticks: u32, mut expiration: X, mut filler: F) -> BenchReport
+where
+    X: FnMut(u32, &T) -> bool,
+    F: FnMut(u32) -> T,
+{
+    let mut cache: Option<T> = None;
+    let mut report = BenchReport::default();
+
+    let start = Instant::now();
+
+    for tick in 0..ticks {
+        let fills = &mut report.fills;
+        let fill_time = &mut report.fill_time;
+        let expiration = &mut expiration;
+        let filler = &mut filler;
+
+        let accessor = (&mut cache).access(
+            |v: &T| expiration(tick, v),
+            || {
+                let fill_start = Instant::now();
+                let value = filler(tick);
+
+                *fills += 1;
+                *fill_time += fill_start.elapsed();
+
+                value
+            },
+        );
+
+        black_box(accessor.take());
+
+        report.accesses += 1;
+    }
+
+    report.total_time = start.elapsed();
+
+    report
+}
+
+pub fn drive_pattern<T, P, F>(pattern: P, mut filler: F) -> BenchReport
+where
+    P: IntoIterator<Item = bool>,
+    F: FnMut() -> T,
+{
+    let misses: Vec<bool> = pattern.into_iter().collect();
+
+    drive_ticks(
+        misses.len() as u32,
+        |tick, _: &T| misses[tick as usize],
+        |_| filler(),
+    )
+}
+
+pub fn every_nth_miss(accesses: usize, n: usize) -> impl Iterator<Item = bool> {
+    (0..accesses).map(move |i| n != 0 && i % n == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drive_pattern_counts_fills_for_the_miss_pattern() {
+        let report = drive_pattern(every_nth_miss(10, 3), || 1u32);
+
+        assert_eq!(report.accesses, 10);
+        assert_eq!(report.fills, 4);
+        assert_eq!(report.hits(), 6);
+        assert!((report.hit_rate() - 0.6).abs() < 1e-9);
+        assert!(report.fill_time <= report.total_time);
+    }
+
+    #[test]
+    fn never_missing_fills_once() {
+        let report = drive_pattern(every_nth_miss(5, 0), || 1u32);
+
+        assert_eq!(report.fills, 1);
+        assert_eq!(report.hits(), 4);
+    }
+
+    #[test]
+    fn drive_ticks_applies_a_ttl_policy() {
+        let report = drive_ticks(10, |tick, filled: &u32| tick - filled >= 4, |tick| tick);
+
+        assert_eq!(report.accesses, 10);
+        assert_eq!(report.fills, 3);
+    }
+
+    #[test]
+    fn empty_reports_have_a_zero_hit_rate() {
+        assert_eq!(BenchReport::default().hit_rate(), 0.0);
+        assert_eq!(drive_pattern(std::iter::empty(), || 1u32).accesses, 0);
+    }
+}
@@ -0,0 +1,244 @@
+use super::*;
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+struct BoundedEntry<V> {
+    data: TickStamped<V>,
+    stamp: Cell<u64>,
+}
+
+pub struct BoundedTtlCacheMap<K, V> {
+    entries: RefCell<HashMap<K, BoundedEntry<V>>>,
+    recency: RefCell<BTreeMap<u64, K>>,
+    next_stamp: Cell<u64>,
+    capacity: usize,
+    ttl: u32,
+}
+
+impl<K, V> BoundedTtlCacheMap<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    pub fn new(capacity: usize, ttl: u32) -> Self {
+        assert!(capacity > 0, "bounded cache capacity must be non-zero");
+
+        BoundedTtlCacheMap {
+            entries: RefCell::new(HashMap::new()),
+            recency: RefCell::new(BTreeMap::new()),
+            next_stamp: Cell::new(0),
+            capacity,
+            ttl,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn ttl(&self) -> u32 {
+        self.ttl
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.borrow().is_empty()
+    }
+
+    fn stamp(&self) -> u64 {
+        let stamp = self.next_stamp.get();
+
+        self.next_stamp.set(stamp + 1);
+
+        stamp
+    }
+
+    pub fn get<Q>(&self, key: &Q, current_tick: u32) -> Option<Ref<'_, V>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let entry = Ref::filter_map(self.entries.borrow(), |e| e.get(key)).ok()?;
+
+        if entry.data.is_older_than(current_tick, self.ttl) {
+            return None;
+        }
+
+        let stamp = self.stamp();
+        let mut recency = self.recency.borrow_mut();
+
+        if let Some(key) = recency.remove(&entry.stamp.replace(stamp)) {
+            recency.insert(stamp, key);
+        }
+
+        drop(recency);
+
+        Some(Ref::map(entry, |e| &e.data.value))
+    }
+
+    pub fn insert(&self, key: K, value: V, current_tick: u32) -> Option<V> {
+        let previous = self.remove(&key);
+
+        if self.len() >= self.capacity {
+            self.sweep_expired(current_tick);
+        }
+
+        let mut entries = self.entries.borrow_mut();
+        let mut recency = self.recency.borrow_mut();
+
+        while entries.len() >= self.capacity {
+            match recency.pop_first() {
+                Some((_, lru)) => entries.remove(&lru),
+                None => break,
+            };
+        }
+
+        let stamp = self.stamp();
+
+        let entry = BoundedEntry {
+            data: TickStamped::new(value, current_tick),
+            stamp: Cell::new(stamp),
+        };
+
+        recency.insert(stamp, key.clone());
+        entries.insert(key, entry);
+
+        previous
+    }
+
+    pub fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let entry = self.entries.borrow_mut().remove(key)?;
+
+        self.recency.borrow_mut().remove(&entry.stamp.get());
+
+        Some(entry.data.value)
+    }
+
+    pub fn sweep_expired(&self, current_tick: u32) -> Vec<(K, V)> {
+        let expired: Vec<_> = self
+            .entries
+            .borrow_mut()
+            .extract_if(|_, e| e.data.is_older_than(current_tick, self.ttl))
+            .collect();
+
+        let mut recency = self.recency.borrow_mut();
+
+        expired
+            .into_iter()
+            .map(|(k, e)| {
+                recency.remove(&e.stamp.get());
+
+                (k, e.data.value)
+            })
+            .collect()
+    }
+
+    pub fn clear(&self) {
+        self.entries.borrow_mut().clear();
+        self.recency.borrow_mut().clear();
+    }
+
+    pub fn get_or_insert_with<F>(&self, key: K, current_tick: u32, filler: F) -> Ref<'_, V>
+    where
+        F: FnOnce() -> V,
+    {
+        if self.get(&key, current_tick).is_none() {
+            let value = filler();
+
+            self.insert(key.clone(), value, current_tick);
+        }
+
+        Ref::map(self.entries.borrow(), |e| &e[&key].data.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_least_recently_used_over_capacity() {
+        let map = BoundedTtlCacheMap::new(2, 100);
+
+        map.insert(1, "a", 0);
+        map.insert(2, "b", 0);
+        map.get(&1, 0);
+        map.insert(3, "c", 0);
+
+        assert!(map.get(&1, 0).is_some());
+        assert!(map.get(&2, 0).is_none());
+        assert!(map.get(&3, 0).is_some());
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn refills_after_ttl_expiry() {
+        let map = BoundedTtlCacheMap::new(4, 5);
+        let fills = Cell::new(0);
+
+        let fill = || {
+            fills.set(fills.get() + 1);
+            fills.get()
+        };
+
+        assert_eq!(*map.get_or_insert_with(1, 0, fill), 1);
+        assert_eq!(*map.get_or_insert_with(1, 4, fill), 1);
+        assert_eq!(*map.get_or_insert_with(1, 5, fill), 2);
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn evicts_expired_entries_before_live_ones() {
+        let map = BoundedTtlCacheMap::new(2, 5);
+
+        map.insert(2, "stale", 0);
+        map.insert(1, "live", 4);
+        map.get(&2, 4);
+        map.insert(3, "new", 6);
+
+        assert!(map.get(&1, 6).is_some());
+        assert!(map.get(&3, 6).is_some());
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn overwriting_keeps_one_entry() {
+        let map = BoundedTtlCacheMap::new(1, 10);
+
+        assert_eq!(map.insert(1, 1, 0), None);
+        assert_eq!(map.insert(1, 2, 0), Some(1));
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.remove(&1), Some(2));
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "non-zero")]
+    fn rejects_zero_capacity() {
+        BoundedTtlCacheMap::<u32, u32>::new(0, 10);
+    }
+
+    #[test]
+    fn sweep_expired_returns_exactly_the_expired_entries() {
+        let map = BoundedTtlCacheMap::new(8, 5);
+
+        map.insert(1, "old", 0);
+        map.insert(2, "older", 1);
+        map.insert(3, "fresh", 4);
+
+        let mut swept = map.sweep_expired(6);
+        swept.sort();
+
+        assert_eq!(swept, vec![(1, "old"), (2, "older")]);
+        assert_eq!(map.len(), 1);
+        assert!(map.get(&3, 6).is_some());
+        assert!(map.sweep_expired(6).is_empty());
+    }
+}
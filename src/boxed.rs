@@ -0,0 +1,173 @@
+use super::*;
+
+trait DynTake<R> {
+    fn take_boxed(self: Box<Self>) -> R;
+}
+
+impl<A, R> DynTake<R> for A
+where
+    A: Get<R>,
+{
+    fn take_boxed(self: Box<Self>) -> R {
+        (*self).take()
+    }
+}
+
+pub struct BoxedAccessor<'a, R> {
+    state: BoxedAccessorState<'a, R>,
+}
+
+enum BoxedAccessorState<'a, R> {
+    Pending(Box<dyn DynTake<R> + 'a>),
+    Forced(R),
+}
+
+impl<'a, R> BoxedAccessor<'a, R> {
+    pub fn new<A>(accessor: A) -> Self
+    where
+        A: Get<R> + 'a,
+    {
+        BoxedAccessor {
+            state: BoxedAccessorState::Pending(Box::new(accessor)),
+        }
+    }
+
+    pub fn is_forced(&self) -> bool {
+        matches!(self.state, BoxedAccessorState::Forced(_))
+    }
+
+    pub fn peek(&self) -> Option<&R> {
+        match &self.state {
+            BoxedAccessorState::Pending(_) => None,
+            BoxedAccessorState::Forced(data) => Some(data),
+        }
+    }
+}
+
+impl<'a, R> Get<R> for BoxedAccessor<'a, R> {
+    fn get(&mut self) -> &R {
+        take_mut::take(&mut self.state, |v| match v {
+            BoxedAccessorState::Pending(accessor) => {
+                BoxedAccessorState::Forced(accessor.take_boxed())
+            }
+            v => v,
+        });
+
+        match &self.state {
+            BoxedAccessorState::Pending(_) => unsafe { std::hint::unreachable_unchecked() },
+            BoxedAccessorState::Forced(data) => data,
+        }
+    }
+
+    fn take(self) -> R {
+        match self.state {
+            BoxedAccessorState::Pending(accessor) => accessor.take_boxed(),
+            BoxedAccessorState::Forced(data) => data,
+        }
+    }
+}
+
+pub struct SendBoxedAccessor<'a, R> {
+    accessor: Box<dyn DynTake<R> + Send + 'a>,
+}
+
+impl<'a, R> SendBoxedAccessor<'a, R> {
+    pub fn new<A>(accessor: A) -> Self
+    where
+        A: Get<R> + Send + 'a,
+    {
+        SendBoxedAccessor {
+            accessor: Box::new(accessor),
+        }
+    }
+
+    pub fn take(self) -> R {
+        self.accessor.take_boxed()
+    }
+
+    pub fn into_boxed(self) -> BoxedAccessor<'a, R> {
+        BoxedAccessor {
+            state: BoxedAccessorState::Pending(self.accessor),
+        }
+    }
+}
+
+pub trait IntoBoxedAccessor<R>: Get<R> {
+    fn boxed<'a>(self) -> BoxedAccessor<'a, R>
+    where
+        Self: Sized + 'a;
+}
+
+impl<A, R> IntoBoxedAccessor<R> for A
+where
+    A: Get<R>,
+{
+    fn boxed<'a>(self) -> BoxedAccessor<'a, R>
+    where
+        Self: Sized + 'a,
+    {
+        BoxedAccessor::new(self)
+    }
+}
+
+pub trait IntoSendBoxedAccessor<R>: Get<R> + Send {
+    fn send_boxed<'a>(self) -> SendBoxedAccessor<'a, R>
+    where
+        Self: Sized + 'a;
+}
+
+impl<A, R> IntoSendBoxedAccessor<R> for A
+where
+    A: Get<R> + Send,
+{
+    fn send_boxed<'a>(self) -> SendBoxedAccessor<'a, R>
+    where
+        Self: Sized + 'a,
+    {
+        SendBoxedAccessor::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn boxed_accessors_fill_only_when_forced() {
+        let mut cache = None;
+        let fills = Cell::new(0);
+
+        let mut boxed = (&mut cache)
+            .access(
+                |_| false,
+                || {
+                    fills.set(fills.get() + 1);
+                    4
+                },
+            )
+            .boxed();
+
+        assert!(!boxed.is_forced());
+        assert!(boxed.peek().is_none());
+        assert_eq!(fills.get(), 0);
+
+        assert_eq!(**boxed.get(), 4);
+        assert_eq!(**boxed.get(), 4);
+        assert!(boxed.is_forced());
+        assert_eq!(boxed.peek().map(|v| **v), Some(4));
+        assert_eq!(fills.get(), 1);
+    }
+
+    #[test]
+    fn send_boxed_accessors_convert_to_boxed() {
+        let mut cache = None;
+
+        let boxed = (&mut cache)
+            .access(|_| false, || 8)
+            .send_boxed()
+            .into_boxed();
+
+        assert!(!boxed.is_forced());
+        assert_eq!(*boxed.take(), 8);
+    }
+}
@@ -0,0 +1,103 @@
+use super::*;
+
+pub struct Cached<T> {
+    pub value: T,
+    pub cache: bool,
+}
+
+impl<T> Cached<T> {
+    pub fn new(value: T, cache: bool) -> Self {
+        Cached { value, cache }
+    }
+
+    pub fn store(value: T) -> Self {
+        Cached::new(value, true)
+    }
+
+    pub fn once(value: T) -> Self {
+        Cached::new(value, false)
+    }
+}
+
+pub trait FastCacheMaybeStoreAccessor<'a, T, R>:
+    FastCacheExpiration<T> + FastCacheMaybeGet<'a, T, R>
+{
+    fn get_or_insert_cached<X, F>(self, expiration: X, filler: F) -> Either<R, T>
+    where
+        X: FnOnce(&T) -> bool,
+        F: FnOnce() -> Cached<T>;
+}
+
+impl<'a, C, T, R> FastCacheMaybeStoreAccessor<'a, T, R> for C
+where
+    C: FastCacheExpiration<T> + FastCacheMaybeGet<'a, T, R>,
+{
+    fn get_or_insert_cached<X, F>(self, expiration: X, filler: F) -> Either<R, T>
+    where
+        X: FnOnce(&T) -> bool,
+        F: FnOnce() -> Cached<T>,
+    {
+        let mut uncached = None;
+
+        let stored = self.expire_with(expiration).maybe_get_or_insert_with(|| {
+            let filled = cold_fill(filler);
+
+            if filled.cache {
+                Some(filled.value)
+            } else {
+                uncached = Some(filled.value);
+
+                None
+            }
+        });
+
+        match (stored, uncached) {
+            (Some(data), _) => Either::Left(data),
+            (None, Some(value)) => Either::Right(value),
+            (None, None) => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uncacheable_values_are_returned_but_not_stored() {
+        let mut cache: Option<u32> = None;
+        let fills = Cell::new(0);
+        let volatile = || {
+            fills.set(fills.get() + 1);
+            Cached::once(fills.get())
+        };
+
+        assert_eq!(
+            (&mut cache).get_or_insert_cached(|_| false, volatile),
+            Either::Right(1)
+        );
+        assert_eq!(cache, None);
+        assert_eq!(
+            (&mut cache).get_or_insert_cached(|_| false, volatile),
+            Either::Right(2)
+        );
+        assert_eq!(fills.get(), 2);
+    }
+
+    #[test]
+    fn cacheable_values_are_stored_and_reused() {
+        let cache = RefCell::new(None);
+
+        let first = (&cache).get_or_insert_cached(|_| false, || Cached::store(5));
+        assert!(matches!(&first, Either::Left(v) if **v == 5));
+        drop(first);
+
+        let second = (&cache).get_or_insert_cached(|_| false, || Cached::store(6));
+        assert!(matches!(&second, Either::Left(v) if **v == 5));
+        drop(second);
+
+        let refreshed = (&cache).get_or_insert_cached(|v| *v == 5, || Cached::new(7, false));
+        assert!(matches!(refreshed, Either::Right(7)));
+        assert!(cache.borrow().is_none());
+    }
+}
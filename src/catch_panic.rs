@@ -0,0 +1,54 @@
+use super::*;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+pub trait FastCacheCatchPanicAccessor<'a, T, R>: FastCacheMaybeAccessor<'a, T, R> {
+    #[allow(clippy::type_complexity)]
+    fn access_catch_panic<X, F>(
+        self,
+        expiration: X,
+        filler: F,
+    ) -> MaybeCacheAccesor<'a, T, Self, X, impl FnOnce() -> Option<T>, R>
+    where
+        F: FnOnce() -> Option<T>,
+        X: FnOnce(&T) -> bool;
+}
+
+impl<'a, C, T, R> FastCacheCatchPanicAccessor<'a, T, R> for C
+where
+    C: FastCacheMaybeAccessor<'a, T, R>,
+{
+    #[allow(clippy::type_complexity)]
+    fn access_catch_panic<X, F>(
+        self,
+        expiration: X,
+        filler: F,
+    ) -> MaybeCacheAccesor<'a, T, Self, X, impl FnOnce() -> Option<T>, R>
+    where
+        F: FnOnce() -> Option<T>,
+        X: FnOnce(&T) -> bool,
+    {
+        self.maybe_access(expiration, move || {
+            catch_unwind(AssertUnwindSafe(filler)).ok().flatten()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panicking_fillers_become_a_miss() {
+        let cache: RefCell<Option<u32>> = RefCell::new(None);
+
+        let value = (&cache)
+            .access_catch_panic(|_| false, || panic!("bad input"))
+            .take();
+
+        assert!(value.is_none());
+        assert!(cache.borrow().is_none());
+
+        let value = (&cache).access_catch_panic(|_| false, || Some(2)).take();
+        assert_eq!(value.map(|v| *v), Some(2));
+    }
+}
@@ -0,0 +1,116 @@
+use super::*;
+
+pub trait AndThenCache<RA>: Get<RA>
+where
+    Self: Sized,
+{
+    fn and_then_cache<B, G, RB>(self, build: G) -> ChainedCacheAccesor<Self, G, B, RA>
+    where
+        G: FnOnce(&RA) -> B,
+        B: Get<RB>;
+}
+
+impl<A, RA> AndThenCache<RA> for A
+where
+    A: Get<RA>,
+{
+    fn and_then_cache<B, G, RB>(self, build: G) -> ChainedCacheAccesor<Self, G, B, RA>
+    where
+        G: FnOnce(&RA) -> B,
+        B: Get<RB>,
+    {
+        ChainedCacheAccesor {
+            state: ChainedCacheState::Pending(self, build),
+            phantom: PhantomData,
+        }
+    }
+}
+
+pub struct ChainedCacheAccesor<A, G, B, RA> {
+    state: ChainedCacheState<A, G, B>,
+    phantom: PhantomData<RA>,
+}
+
+pub enum ChainedCacheState<A, G, B> {
+    Pending(A, G),
+    Built(B),
+}
+
+impl<A, G, B> ChainedCacheState<A, G, B> {
+    pub fn into_built<RA>(self) -> Self
+    where
+        A: Get<RA>,
+        G: FnOnce(&RA) -> B,
+    {
+        match self {
+            ChainedCacheState::Pending(mut first, build) => {
+                ChainedCacheState::Built(build(first.get()))
+            }
+            v => v,
+        }
+    }
+}
+
+impl<A, G, B, RA, RB> Get<RB> for ChainedCacheAccesor<A, G, B, RA>
+where
+    A: Get<RA>,
+    G: FnOnce(&RA) -> B,
+    B: Get<RB>,
+{
+    fn get(&mut self) -> &RB {
+        take_mut::take(&mut self.state, |v| v.into_built());
+
+        match &mut self.state {
+            ChainedCacheState::Pending(_, _) => unsafe { std::hint::unreachable_unchecked() },
+            ChainedCacheState::Built(second) => second.get(),
+        }
+    }
+
+    fn take(self) -> RB {
+        match self.state.into_built() {
+            ChainedCacheState::Pending(_, _) => unsafe { std::hint::unreachable_unchecked() },
+            ChainedCacheState::Built(second) => second.take(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forces_the_first_cache_before_the_second() {
+        let log = RefCell::new(Vec::new());
+        let log = &log;
+        let mut first = None;
+        let mut second = None;
+
+        let mut chained = (&mut first)
+            .access(
+                |_| false,
+                || {
+                    log.borrow_mut().push("a");
+                    2
+                },
+            )
+            .and_then_cache(|a: &&u32| {
+                let a = **a;
+
+                (&mut second).access(
+                    |_| false,
+                    move || {
+                        log.borrow_mut().push("b");
+                        a * 10
+                    },
+                )
+            });
+
+        assert!(log.borrow().is_empty());
+        assert_eq!(**chained.get(), 20);
+        assert_eq!(**chained.get(), 20);
+        assert_eq!(*chained.take(), 20);
+
+        assert_eq!(*log.borrow(), vec!["a", "b"]);
+        assert_eq!((first, second), (Some(2), Some(20)));
+    }
+}
@@ -0,0 +1,82 @@
+use super::*;
+
+pub trait FastCacheFillIfOtherAccessor<'a, T, R>: FastCacheMaybeAccessor<'a, T, R> {
+    #[allow(clippy::type_complexity)]
+    fn fill_if_other<'o, A, P, X, F>(
+        self,
+        other: &'o Option<A>,
+        cond: P,
+        expiration: X,
+        filler: F,
+    ) -> MaybeCacheAccesor<'a, T, Self, X, impl FnOnce() -> Option<T> + 'o, R>
+    where
+        P: Fn(&A) -> bool + 'o,
+        X: FnOnce(&T) -> bool,
+        F: FnOnce(&A) -> T + 'o;
+}
+
+impl<'a, C, T, R> FastCacheFillIfOtherAccessor<'a, T, R> for C
+where
+    C: FastCacheMaybeAccessor<'a, T, R>,
+{
+    #[allow(clippy::type_complexity)]
+    fn fill_if_other<'o, A, P, X, F>(
+        self,
+        other: &'o Option<A>,
+        cond: P,
+        expiration: X,
+        filler: F,
+    ) -> MaybeCacheAccesor<'a, T, Self, X, impl FnOnce() -> Option<T> + 'o, R>
+    where
+        P: Fn(&A) -> bool + 'o,
+        X: FnOnce(&T) -> bool,
+        F: FnOnce(&A) -> T + 'o,
+    {
+        self.maybe_access(expiration, move || {
+            other.as_ref().filter(|a| cond(a)).map(filler)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_only_when_the_other_cache_meets_the_condition() {
+        let mut energy: Option<u32> = None;
+        let mut plan: Option<String> = None;
+
+        let fill = |energy: &u32| format!("spawn with {}", energy);
+
+        assert!((&mut plan)
+            .fill_if_other(&energy, |e| *e >= 300, |_| false, fill)
+            .take()
+            .is_none());
+
+        energy = Some(200);
+        assert!((&mut plan)
+            .fill_if_other(&energy, |e| *e >= 300, |_| false, fill)
+            .take()
+            .is_none());
+        assert!(plan.is_none());
+
+        energy = Some(300);
+        assert_eq!(
+            (&mut plan)
+                .fill_if_other(&energy, |e| *e >= 300, |_| false, fill)
+                .take()
+                .map(String::as_str),
+            Some("spawn with 300")
+        );
+
+        energy = None;
+        assert_eq!(
+            (&mut plan)
+                .fill_if_other(&energy, |e| *e >= 300, |_| false, fill)
+                .take()
+                .map(String::as_str),
+            Some("spawn with 300")
+        );
+    }
+}
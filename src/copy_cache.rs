@@ -0,0 +1,88 @@
+use super::*;
+
+#[derive(Clone, Copy)]
+pub struct CopyCache<T: Copy> {
+    value: Option<T>,
+}
+
+impl<T: Copy> Default for CopyCache<T> {
+    fn default() -> Self {
+        CopyCache { value: None }
+    }
+}
+
+impl<T: Copy> CopyCache<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn get(&self) -> Option<T> {
+        self.value
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.value = None;
+    }
+
+    #[inline(always)]
+    pub fn get_or_insert_with<X, F>(&mut self, expiration: X, filler: F) -> T
+    where
+        X: FnOnce(&T) -> bool,
+        F: FnOnce() -> T,
+    {
+        if let Some(value) = self.value {
+            if !expiration(&value) {
+                return value;
+            }
+        }
+
+        let value = cold_fill(filler);
+
+        self.value = Some(value);
+
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_once_and_refills_on_expiry() {
+        let mut cache = CopyCache::new();
+        let fills = Cell::new(0);
+        let fill = |value: u32| {
+            let fills = &fills;
+
+            move || {
+                fills.set(fills.get() + 1);
+                value
+            }
+        };
+
+        assert_eq!(cache.get(), None);
+        assert_eq!(cache.get_or_insert_with(|_| false, fill(1)), 1);
+        assert_eq!(cache.get_or_insert_with(|_| false, fill(2)), 1);
+        assert_eq!(cache.get_or_insert_with(|v| *v == 1, fill(3)), 3);
+        assert_eq!(cache.get(), Some(3));
+        assert_eq!(fills.get(), 2);
+
+        cache.clear();
+        assert_eq!(cache.get(), None);
+    }
+
+    #[test]
+    fn copies_are_independent() {
+        let mut original = CopyCache::new();
+        original.get_or_insert_with(|_| false, || 5u8);
+
+        let mut copy = original;
+        copy.clear();
+
+        assert_eq!(original.get(), Some(5));
+        assert_eq!(copy.get(), None);
+    }
+}
@@ -0,0 +1,132 @@
+use super::*;
+use dashmap::DashMap;
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::sync::{Arc, OnceLock};
+
+pub struct DashCacheMap<K, V>
+where
+    K: Hash + Eq,
+{
+    entries: DashMap<K, Arc<OnceLock<V>>>,
+}
+
+impl<K, V> Default for DashCacheMap<K, V>
+where
+    K: Hash + Eq,
+{
+    fn default() -> Self {
+        DashCacheMap {
+            entries: DashMap::new(),
+        }
+    }
+}
+
+impl<K, V> DashCacheMap<K, V>
+where
+    K: Hash + Eq,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<SyncCacheRef<V>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let cell = self.entries.get(key)?.clone();
+
+        cell.get()?;
+
+        Some(SyncCacheRef::from_cell(cell))
+    }
+
+    pub fn insert(&self, key: K, value: V) {
+        self.entries.insert(key, Arc::new(OnceLock::from(value)));
+    }
+
+    pub fn remove<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.entries.remove(key).is_some()
+    }
+
+    pub fn clear(&self) {
+        self.entries.clear();
+    }
+
+    pub fn get_or_insert_with<F>(&self, key: K, filler: F) -> SyncCacheRef<V>
+    where
+        F: FnOnce() -> V,
+    {
+        let existing = self.entries.get(&key).map(|cell| cell.clone());
+
+        let cell = existing.unwrap_or_else(|| self.entries.entry(key).or_default().clone());
+
+        cell.get_or_init(filler);
+
+        SyncCacheRef::from_cell(cell)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Barrier;
+    use std::thread;
+
+    #[test]
+    fn fills_once_per_key_across_threads() {
+        let map = DashCacheMap::new();
+        let fills: Vec<AtomicUsize> = (0..16).map(|_| AtomicUsize::new(0)).collect();
+        let barrier = Barrier::new(8);
+
+        thread::scope(|scope| {
+            for _ in 0..8 {
+                let (map, fills, barrier) = (&map, &fills, &barrier);
+
+                scope.spawn(move || {
+                    barrier.wait();
+
+                    for (key, count) in fills.iter().enumerate() {
+                        let value = map.get_or_insert_with(key, || {
+                            count.fetch_add(1, Ordering::SeqCst);
+                            key * 3
+                        });
+
+                        assert_eq!(*value, key * 3);
+                    }
+                });
+            }
+        });
+
+        assert!(fills.iter().all(|f| f.load(Ordering::SeqCst) == 1));
+        assert_eq!(map.len(), 16);
+    }
+
+    #[test]
+    fn insert_get_and_remove() {
+        let map = DashCacheMap::new();
+
+        assert!(map.get("a").is_none());
+
+        map.insert(String::from("a"), 1);
+        assert_eq!(*map.get("a").unwrap(), 1);
+        assert_eq!(*map.get_or_insert_with(String::from("a"), || 2), 1);
+
+        assert!(map.remove("a"));
+        assert!(map.is_empty());
+    }
+}
@@ -0,0 +1,93 @@
+use super::*;
+
+pub trait FastCacheDeadlineAccessor<'a, T, R>: FastCacheMaybeAccessor<'a, T, R> {
+    #[allow(clippy::type_complexity)]
+    fn fill_with_deadline<X, D, F>(
+        self,
+        expiration: X,
+        deadline_check: D,
+        filler: F,
+    ) -> MaybeCacheAccesor<'a, T, Self, X, impl FnOnce() -> Option<T>, R>
+    where
+        X: FnOnce(&T) -> bool,
+        D: Fn() -> bool,
+        F: FnOnce(&dyn Fn() -> bool) -> Option<T>;
+}
+
+impl<'a, C, T, R> FastCacheDeadlineAccessor<'a, T, R> for C
+where
+    C: FastCacheMaybeAccessor<'a, T, R>,
+{
+    #[allow(clippy::type_complexity)]
+    fn fill_with_deadline<X, D, F>(
+        self,
+        expiration: X,
+        deadline_check: D,
+        filler: F,
+    ) -> MaybeCacheAccesor<'a, T, Self, X, impl FnOnce() -> Option<T>, R>
+    where
+        X: FnOnce(&T) -> bool,
+        D: Fn() -> bool,
+        F: FnOnce(&dyn Fn() -> bool) -> Option<T>,
+    {
+        // A filler that bails out returns `None`, which leaves the slot empty
+        // so the next access retries the fill.
+        self.maybe_access(expiration, move || filler(&deadline_check))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sum_until(deadline: &dyn Fn() -> bool, steps: &Cell<u32>) -> Option<u32> {
+        let mut total = 0;
+
+        for i in 1..=5 {
+            if deadline() {
+                return None;
+            }
+
+            steps.set(steps.get() + 1);
+            total += i;
+        }
+
+        Some(total)
+    }
+
+    #[test]
+    fn bailing_out_leaves_the_slot_empty() {
+        let cache: RefCell<Option<u32>> = RefCell::new(None);
+        let steps = Cell::new(0);
+
+        let value = (&cache)
+            .fill_with_deadline(|_| false, || steps.get() >= 2, |d| sum_until(d, &steps))
+            .take();
+
+        assert!(value.is_none());
+        assert_eq!(steps.get(), 2);
+        assert!(cache.borrow().is_none());
+    }
+
+    #[test]
+    fn completing_within_the_deadline_fills_the_slot() {
+        let mut cache = None;
+        let steps = Cell::new(0);
+
+        let value = (&mut cache)
+            .fill_with_deadline(|_| false, || false, |d| sum_until(d, &steps))
+            .take()
+            .copied();
+
+        assert_eq!(value, Some(15));
+        assert_eq!(cache, Some(15));
+
+        let value = (&mut cache)
+            .fill_with_deadline(|_| false, || true, |d| sum_until(d, &steps))
+            .take()
+            .copied();
+
+        assert_eq!(value, Some(15));
+        assert_eq!(steps.get(), 5);
+    }
+}
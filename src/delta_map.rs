@@ -0,0 +1,196 @@
+use super::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::hash::Hash;
+
+#[derive(Serialize, Deserialize)]
+pub struct CacheDelta<K, V> {
+    pub changed: Vec<(K, V)>,
+    pub removed: Vec<K>,
+}
+
+impl<K, V> CacheDelta<K, V> {
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+pub struct DeltaCacheMap<K, V> {
+    map: CacheMap<K, V>,
+    dirty: RefCell<HashSet<K>>,
+}
+
+impl<K, V> Default for DeltaCacheMap<K, V> {
+    fn default() -> Self {
+        DeltaCacheMap {
+            map: CacheMap::default(),
+            dirty: RefCell::new(HashSet::new()),
+        }
+    }
+}
+
+impl<K, V> DeltaCacheMap<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn dirty_len(&self) -> usize {
+        self.dirty.borrow().len()
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<Ref<'_, V>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.get(key)
+    }
+
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        self.dirty.borrow_mut().insert(key.clone());
+
+        self.map.insert(key, value)
+    }
+
+    pub fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (key, value) = self.map.remove_entry(key)?;
+
+        self.dirty.borrow_mut().insert(key);
+
+        Some(value)
+    }
+
+    pub fn get_or_insert_with<F>(&self, key: K, filler: F) -> Ref<'_, V>
+    where
+        F: FnOnce() -> V,
+    {
+        let (value, filled) = self.map.get_or_insert_with_tracked(key.clone(), filler);
+
+        if filled {
+            self.dirty.borrow_mut().insert(key);
+        }
+
+        value
+    }
+
+    pub fn take_delta(&self) -> CacheDelta<K, V>
+    where
+        V: Clone,
+    {
+        let mut delta = CacheDelta {
+            changed: Vec::new(),
+            removed: Vec::new(),
+        };
+
+        for key in self.dirty.borrow_mut().drain() {
+            match self.map.get(&key) {
+                Some(value) => {
+                    let value = value.clone();
+
+                    delta.changed.push((key, value));
+                }
+                None => delta.removed.push(key),
+            }
+        }
+
+        delta
+    }
+
+    pub fn take_delta_json(&self) -> Result<Value, serde_json::Error>
+    where
+        K: Serialize,
+        V: Serialize + Clone,
+    {
+        serde_json::to_value(self.take_delta())
+    }
+
+    pub fn apply_delta(&self, delta: CacheDelta<K, V>) {
+        for key in delta.removed {
+            self.map.remove(&key);
+        }
+
+        for (key, value) in delta.changed {
+            self.map.insert(key, value);
+        }
+    }
+
+    pub fn apply_delta_json(&self, delta: Value) -> Result<(), serde_json::Error>
+    where
+        K: DeserializeOwned,
+        V: DeserializeOwned,
+    {
+        self.apply_delta(serde_json::from_value(delta)?);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delta_covers_changes_since_the_last_take() {
+        let map = DeltaCacheMap::new();
+
+        map.insert("a".to_string(), 1);
+        map.get_or_insert_with("b".to_string(), || 2);
+
+        let mut delta = map.take_delta();
+        delta.changed.sort();
+
+        assert_eq!(
+            delta.changed,
+            vec![("a".to_string(), 1), ("b".to_string(), 2)]
+        );
+        assert!(delta.removed.is_empty());
+        assert_eq!(map.dirty_len(), 0);
+
+        map.get_or_insert_with("a".to_string(), || 9);
+        map.remove("b");
+
+        let delta = map.take_delta();
+
+        assert!(delta.changed.is_empty());
+        assert_eq!(delta.removed, vec!["b".to_string()]);
+    }
+
+    #[test]
+    fn applies_a_json_delta() {
+        let source = DeltaCacheMap::new();
+        let replica = DeltaCacheMap::new();
+
+        source.insert(1, "one".to_string());
+        replica
+            .apply_delta_json(source.take_delta_json().unwrap())
+            .unwrap();
+
+        replica.insert(2, "two".to_string());
+        source.remove(&1);
+        replica
+            .apply_delta_json(source.take_delta_json().unwrap())
+            .unwrap();
+
+        assert!(replica.get(&1).is_none());
+        assert_eq!(replica.get(&2).as_deref(), Some(&"two".to_string()));
+        assert_eq!(replica.len(), 1);
+    }
+}
@@ -0,0 +1,91 @@
+use super::*;
+use std::sync::Arc;
+
+impl<'c, T, C, X, F> CacheAccesor<'c, T, C, X, F, Ref<'c, T>>
+where
+    F: FnOnce() -> T,
+    X: FnOnce(&T) -> bool,
+    C: FastCacheGet<'c, T, Ref<'c, T>> + FastCacheExpiration<T>,
+{
+    pub fn detach(&mut self) -> Ref<'c, T> {
+        Ref::clone(self.get())
+    }
+
+    // Only a forced accessor hands out borrows here; an unforced one would
+    // have to expire and fill, which needs `&mut self`.
+    pub fn borrow_shared(&self) -> Option<Ref<'c, T>> {
+        match &self.state {
+            CacheState::Unknown(_, _) => None,
+            CacheState::Known(s) => Some(Ref::clone(&s.data)),
+        }
+    }
+}
+
+impl<'c, T, C, X, F> CacheAccesor<'c, Arc<T>, C, X, F, Arc<T>>
+where
+    F: FnOnce() -> Arc<T>,
+    X: FnOnce(&Arc<T>) -> bool,
+    C: FastCacheGet<'c, Arc<T>, Arc<T>> + FastCacheExpiration<Arc<T>>,
+{
+    pub fn detach(&mut self) -> Arc<T> {
+        self.get().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn arc_handles_outlive_the_accessor() {
+        let cache: Mutex<Option<Arc<String>>> = Mutex::new(None);
+
+        let mut accessor = (&cache).access(|_| false, || Arc::new("route".to_string()));
+        let handle = accessor.detach();
+        let moved = accessor;
+        drop(moved);
+
+        assert_eq!(*handle, "route");
+        assert_eq!(Arc::strong_count(&handle), 2);
+
+        cache.lock().unwrap().take();
+        assert_eq!(Arc::strong_count(&handle), 1);
+        assert_eq!(*handle, "route");
+    }
+
+    #[test]
+    fn ref_handles_outlive_the_accessor() {
+        let cache = RefCell::new(None);
+
+        let handle = {
+            let mut accessor = (&cache).access(|_| false, || vec![1, 2]);
+
+            accessor.detach()
+        };
+
+        assert_eq!(*handle, vec![1, 2]);
+        assert!(cache.try_borrow_mut().is_err());
+
+        drop(handle);
+        assert!(cache.try_borrow_mut().is_ok());
+    }
+
+    struct Handle(u32);
+
+    #[test]
+    fn borrow_shared_hands_out_concurrent_refs() {
+        let cache = RefCell::new(None);
+        let mut accessor = (&cache).access(|_| false, || Handle(7));
+
+        assert!(accessor.borrow_shared().is_none());
+
+        let first = accessor.detach();
+        let second = accessor.borrow_shared().unwrap();
+        let third = accessor.borrow_shared().unwrap();
+
+        assert_eq!(first.0 + second.0 + third.0, 21);
+        assert!(std::ptr::eq(&*first, &*second));
+        assert!(std::ptr::eq(&*second, &*third));
+    }
+}
@@ -0,0 +1,93 @@
+use super::*;
+
+// Struct fields drop in declaration order, which is easy to break when a
+// cached view must not outlive the handle it borrows from. Calling these from
+// the owner's `Drop` impl empties the caches in an explicit order instead.
+pub fn drop_cached<C, T>(cache: C)
+where
+    C: FastCacheBackend<T>,
+{
+    // The value is moved out before dropping so that its destructor does not
+    // run while a `RefCell` or `Mutex` backend is still borrowed.
+    let value = cache.map_backend(Option::take);
+
+    drop(value);
+}
+
+pub fn drop_in_order<A, TA, B, TB>(first: A, second: B)
+where
+    A: FastCacheBackend<TA>,
+    B: FastCacheBackend<TB>,
+{
+    drop_cached(first);
+    drop_cached(second);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::{Rc, Weak};
+
+    struct Logged(&'static str, Rc<RefCell<Vec<&'static str>>>);
+
+    impl Drop for Logged {
+        fn drop(&mut self) {
+            self.1.borrow_mut().push(self.0);
+        }
+    }
+
+    struct Resources {
+        handle: RefCell<Option<Logged>>,
+        view: RefCell<Option<Logged>>,
+    }
+
+    impl Drop for Resources {
+        fn drop(&mut self) {
+            drop_in_order(&self.view, &self.handle);
+        }
+    }
+
+    #[test]
+    fn drop_in_order_overrides_declaration_order() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        let resources = Resources {
+            handle: RefCell::new(None),
+            view: RefCell::new(None),
+        };
+
+        let _ = (&resources.handle)
+            .access(|_| false, || Logged("handle", log.clone()))
+            .take();
+        let _ = (&resources.view)
+            .access(|_| false, || Logged("view", log.clone()))
+            .take();
+
+        drop(resources);
+
+        assert_eq!(*log.borrow(), vec!["view", "handle"]);
+    }
+
+    struct Reentrant(Weak<RefCell<Option<Reentrant>>>, Rc<Cell<bool>>);
+
+    impl Drop for Reentrant {
+        fn drop(&mut self) {
+            let cache = self.0.upgrade().unwrap();
+
+            self.1.set(cache.try_borrow_mut().is_ok());
+        }
+    }
+
+    #[test]
+    fn drop_cached_releases_the_backend_before_dropping() {
+        let released = Rc::new(Cell::new(false));
+        let cache = Rc::new(RefCell::new(None));
+
+        *cache.borrow_mut() = Some(Reentrant(Rc::downgrade(&cache), released.clone()));
+
+        drop_cached(&*cache);
+
+        assert!(released.get());
+        assert!(cache.borrow().is_none());
+    }
+}
@@ -0,0 +1,64 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+impl<L, R> Either<L, R> {
+    pub fn is_left(&self) -> bool {
+        matches!(self, Either::Left(_))
+    }
+
+    pub fn is_right(&self) -> bool {
+        matches!(self, Either::Right(_))
+    }
+}
+
+impl<'c, T, C, X, F, R> CacheAccesor<'c, T, C, X, F, R>
+where
+    F: FnOnce() -> T,
+    X: FnOnce(&T) -> bool,
+    C: FastCacheGet<'c, T, R> + FastCacheExpiration<T>,
+{
+    pub fn get_or_compute_uncached<G>(&mut self, use_cache: bool, filler: G) -> Either<&R, T>
+    where
+        G: FnOnce() -> T,
+    {
+        if use_cache {
+            Either::Left(self.get())
+        } else {
+            Either::Right(filler())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn use_cache_returns_the_cached_reference() {
+        let mut cache = None;
+        let mut accessor = (&mut cache).access(|_| false, || 1);
+
+        let result = accessor.get_or_compute_uncached(true, || 2);
+
+        assert!(result.is_left());
+        assert_eq!(result, Either::Left(&&1));
+        assert_eq!(cache, Some(1));
+    }
+
+    #[test]
+    fn uncached_returns_a_fresh_value_without_storing_it() {
+        let mut cache = Some(1);
+        let mut accessor = (&mut cache).access(|_| true, || unreachable!());
+
+        let result = accessor.get_or_compute_uncached(false, || 2);
+
+        assert!(result.is_right());
+        assert_eq!(result, Either::Right(2));
+        assert_eq!(cache, Some(1));
+    }
+}
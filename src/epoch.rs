@@ -0,0 +1,133 @@
+use super::*;
+
+const NO_EPOCH: u64 = u64::MAX;
+
+pub struct EpochCache<T> {
+    epoch: u64,
+    value: Option<T>,
+}
+
+impl<T> Default for EpochCache<T> {
+    fn default() -> Self {
+        EpochCache {
+            epoch: NO_EPOCH,
+            value: None,
+        }
+    }
+}
+
+impl<T> EpochCache<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn epoch(&self) -> Option<u32> {
+        if self.epoch == NO_EPOCH {
+            None
+        } else {
+            Some(self.epoch as u32)
+        }
+    }
+
+    pub fn invalidate(&mut self) {
+        self.epoch = NO_EPOCH;
+    }
+
+    #[inline]
+    pub fn get(&self, epoch: u32) -> Option<&T> {
+        if self.epoch == epoch as u64 {
+            self.value.as_ref()
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    pub fn get_or_insert_with<F>(&mut self, epoch: u32, filler: F) -> &T
+    where
+        F: FnOnce() -> T,
+    {
+        // A hit costs the epoch compare plus the discriminant check on
+        // `value`: the epoch only matches after a fill has stored a value,
+        // but safe code cannot tell the compiler so. That second branch is
+        // always taken the same way on a hit and so predicts perfectly.
+        if self.epoch == epoch as u64 {
+            match &self.value {
+                Some(value) => value,
+                None => unreachable!(),
+            }
+        } else {
+            self.refill(epoch, filler)
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn refill<F>(&mut self, epoch: u32, filler: F) -> &T
+    where
+        F: FnOnce() -> T,
+    {
+        self.epoch = NO_EPOCH;
+
+        let value = cold_fill(filler);
+
+        self.epoch = epoch as u64;
+        self.value.insert(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    #[test]
+    fn refills_only_when_the_epoch_changes() {
+        let mut cache = EpochCache::new();
+        let fills = Cell::new(0);
+        let at = |cache: &mut EpochCache<u32>, epoch: u32| {
+            *cache.get_or_insert_with(epoch, || {
+                fills.set(fills.get() + 1);
+                epoch * 2
+            })
+        };
+
+        assert_eq!(cache.epoch(), None);
+        assert_eq!(at(&mut cache, 1), 2);
+        assert_eq!(at(&mut cache, 1), 2);
+        assert_eq!(at(&mut cache, 2), 4);
+        assert_eq!(cache.epoch(), Some(2));
+        assert_eq!(cache.get(2), Some(&4));
+        assert_eq!(cache.get(1), None);
+
+        cache.invalidate();
+        assert_eq!(cache.get(2), None);
+        assert_eq!(at(&mut cache, 2), 4);
+        assert_eq!(fills.get(), 3);
+    }
+
+    #[test]
+    fn the_maximum_epoch_is_a_valid_epoch() {
+        let mut cache = EpochCache::new();
+
+        assert_eq!(*cache.get_or_insert_with(u32::MAX, || 1), 1);
+        assert_eq!(*cache.get_or_insert_with(u32::MAX, || 2), 1);
+        assert_eq!(cache.epoch(), Some(u32::MAX));
+    }
+
+    #[test]
+    fn a_panicking_fill_leaves_the_cache_invalid() {
+        let mut cache = EpochCache::new();
+
+        cache.get_or_insert_with(1, || 1);
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            cache.get_or_insert_with(2, || panic!("fill failed"));
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(cache.epoch(), None);
+        assert_eq!(cache.get(1), None);
+        assert_eq!(*cache.get_or_insert_with(2, || 3), 3);
+    }
+}
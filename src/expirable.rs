@@ -0,0 +1,195 @@
+use super::*;
+
+pub struct Expirable<T> {
+    pub value: T,
+    expired: Cell<bool>,
+}
+
+impl<T> Expirable<T> {
+    pub fn new(value: T) -> Self {
+        Expirable {
+            value,
+            expired: Cell::new(false),
+        }
+    }
+
+    pub fn mark_expired(&self) {
+        self.expired.set(true);
+    }
+
+    pub fn is_marked_expired(&self) -> bool {
+        self.expired.get()
+    }
+}
+
+pub struct RefreshGuard<'g, T> {
+    data: &'g Expirable<T>,
+}
+
+impl<'g, T> Deref for RefreshGuard<'g, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data.value
+    }
+}
+
+impl<'g, T> Drop for RefreshGuard<'g, T> {
+    fn drop(&mut self) {
+        self.data.mark_expired();
+    }
+}
+
+impl<'c, T, C, X, F, R> CacheAccesor<'c, Expirable<T>, C, X, F, R>
+where
+    F: FnOnce() -> Expirable<T>,
+    X: FnOnce(&Expirable<T>) -> bool,
+    C: FastCacheGet<'c, Expirable<T>, R> + FastCacheExpiration<Expirable<T>>,
+    R: Deref<Target = Expirable<T>>,
+{
+    pub fn guard(&mut self) -> RefreshGuard<'_, T> {
+        RefreshGuard {
+            data: self.get().deref(),
+        }
+    }
+}
+
+pub trait FastCacheExpirableAccessor<'a, T, R>: FastCacheAccessor<'a, Expirable<T>, R> {
+    #[allow(clippy::type_complexity)]
+    fn access_expirable<X, F>(
+        self,
+        expiration: X,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        Expirable<T>,
+        Self,
+        impl FnOnce(&Expirable<T>) -> bool,
+        impl FnOnce() -> Expirable<T>,
+        R,
+    >
+    where
+        F: FnOnce() -> T,
+        X: FnOnce(&T) -> bool;
+
+    #[allow(clippy::type_complexity)]
+    fn access_single_use<F>(
+        self,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        Expirable<T>,
+        Self,
+        impl FnOnce(&Expirable<T>) -> bool,
+        impl FnOnce() -> Expirable<T>,
+        R,
+    >
+    where
+        F: FnOnce() -> T;
+}
+
+impl<'a, C, T, R> FastCacheExpirableAccessor<'a, T, R> for C
+where
+    C: FastCacheAccessor<'a, Expirable<T>, R>,
+{
+    #[allow(clippy::type_complexity)]
+    fn access_expirable<X, F>(
+        self,
+        expiration: X,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        Expirable<T>,
+        Self,
+        impl FnOnce(&Expirable<T>) -> bool,
+        impl FnOnce() -> Expirable<T>,
+        R,
+    >
+    where
+        F: FnOnce() -> T,
+        X: FnOnce(&T) -> bool,
+    {
+        self.access(
+            move |v: &Expirable<T>| v.is_marked_expired() || expiration(&v.value),
+            move || Expirable::new(filler()),
+        )
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn access_single_use<F>(
+        self,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        Expirable<T>,
+        Self,
+        impl FnOnce(&Expirable<T>) -> bool,
+        impl FnOnce() -> Expirable<T>,
+        R,
+    >
+    where
+        F: FnOnce() -> T,
+    {
+        self.access(
+            |v: &Expirable<T>| v.is_marked_expired(),
+            move || {
+                let v = Expirable::new(filler());
+
+                v.mark_expired();
+
+                v
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dropping_the_guard_refills_on_the_next_get() {
+        let mut cache = None;
+
+        {
+            let mut accessor = (&mut cache).access_expirable(|_| false, || 1);
+            let guard = accessor.guard();
+
+            assert_eq!(*guard, 1);
+        }
+
+        assert!(cache
+            .as_ref()
+            .map(|v| v.is_marked_expired())
+            .unwrap_or(false));
+
+        let value = (&mut cache).access_expirable(|_| false, || 2).take().value;
+
+        assert_eq!(value, 2);
+
+        let value = (&mut cache).access_expirable(|_| false, || 3).take().value;
+
+        assert_eq!(value, 2);
+    }
+
+    #[test]
+    fn single_use_values_refill_on_the_second_access() {
+        let mut cache = None;
+        let fills = Cell::new(0);
+
+        let fill = || {
+            fills.set(fills.get() + 1);
+            fills.get()
+        };
+
+        let mut accessor = (&mut cache).access_single_use(fill);
+
+        assert_eq!(accessor.get().value, 1);
+        assert_eq!(accessor.get().value, 1);
+
+        drop(accessor);
+
+        assert_eq!((&mut cache).access_single_use(fill).take().value, 2);
+        assert_eq!((&mut cache).access_single_use(fill).take().value, 3);
+    }
+}
@@ -0,0 +1,192 @@
+use super::*;
+use std::marker::PhantomData;
+
+pub trait TryGet<T, E> {
+    fn get(&mut self) -> Result<&T, &E>;
+}
+
+pub trait TryValue<T, E> {
+    fn as_result(&self) -> Result<&T, &E>;
+}
+
+impl<T, E> TryValue<T, E> for Result<T, E> {
+    fn as_result(&self) -> Result<&T, &E> {
+        self.as_ref()
+    }
+}
+
+impl<T, E> TryValue<T, E> for TickStamped<Result<T, E>> {
+    fn as_result(&self) -> Result<&T, &E> {
+        self.value.as_ref()
+    }
+}
+
+pub struct TryCacheAccesor<A, R> {
+    accessor: A,
+    phantom: PhantomData<R>,
+}
+
+impl<A, R, T, E> TryGet<T, E> for TryCacheAccesor<A, R>
+where
+    A: Get<R>,
+    R: Deref,
+    R::Target: TryValue<T, E>,
+{
+    fn get(&mut self) -> Result<&T, &E> {
+        self.accessor.get().deref().as_result()
+    }
+}
+
+pub trait FastCacheTryAccessor<'a, T, E, R>: FastCacheAccessor<'a, Result<T, E>, R> {
+    #[allow(clippy::type_complexity)]
+    fn try_access<X, F>(
+        self,
+        expiration: X,
+        filler: F,
+    ) -> TryCacheAccesor<CacheAccesor<'a, Result<T, E>, Self, X, F, R>, R>
+    where
+        F: FnOnce() -> Result<T, E>,
+        X: FnOnce(&Result<T, E>) -> bool;
+}
+
+impl<'a, C, T, E, R> FastCacheTryAccessor<'a, T, E, R> for C
+where
+    C: FastCacheAccessor<'a, Result<T, E>, R>,
+{
+    #[allow(clippy::type_complexity)]
+    fn try_access<X, F>(
+        self,
+        expiration: X,
+        filler: F,
+    ) -> TryCacheAccesor<CacheAccesor<'a, Result<T, E>, Self, X, F, R>, R>
+    where
+        F: FnOnce() -> Result<T, E>,
+        X: FnOnce(&Result<T, E>) -> bool,
+    {
+        TryCacheAccesor {
+            accessor: self.access(expiration, filler),
+            phantom: PhantomData,
+        }
+    }
+}
+
+pub trait FastCacheErrorTtlAccessor<'a, T, E, R>:
+    FastCacheAccessor<'a, TickStamped<Result<T, E>>, R> + 'a
+{
+    fn try_access_with_error_ttl<F>(
+        self,
+        current_tick: u32,
+        ok_ttl: u32,
+        err_ttl: u32,
+        filler: F,
+    ) -> TryCacheAccesor<impl Get<R>, R>
+    where
+        F: FnOnce() -> Result<T, E>;
+}
+
+impl<'a, C, T, E, R> FastCacheErrorTtlAccessor<'a, T, E, R> for C
+where
+    C: FastCacheAccessor<'a, TickStamped<Result<T, E>>, R> + 'a,
+{
+    fn try_access_with_error_ttl<F>(
+        self,
+        current_tick: u32,
+        ok_ttl: u32,
+        err_ttl: u32,
+        filler: F,
+    ) -> TryCacheAccesor<impl Get<R>, R>
+    where
+        F: FnOnce() -> Result<T, E>,
+    {
+        let accessor = self.access(
+            move |s: &TickStamped<Result<T, E>>| {
+                let ttl = if s.value.is_ok() { ok_ttl } else { err_ttl };
+
+                s.is_older_than(current_tick, ttl)
+            },
+            move || TickStamped::new(filler(), current_tick),
+        );
+
+        TryCacheAccesor {
+            accessor,
+            phantom: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Slot = Option<TickStamped<Result<u32, String>>>;
+
+    fn fetch(cache: &mut Slot, tick: u32, result: Result<u32, String>) -> Result<u32, String> {
+        cache
+            .try_access_with_error_ttl(tick, 10, 2, move || result)
+            .get()
+            .copied()
+            .map_err(|e| e.clone())
+    }
+
+    #[test]
+    fn errors_expire_on_the_error_ttl() {
+        let mut cache: Slot = None;
+
+        assert_eq!(fetch(&mut cache, 0, Err("down".into())), Err("down".into()));
+        assert_eq!(fetch(&mut cache, 1, Ok(1)), Err("down".into()));
+        assert_eq!(fetch(&mut cache, 2, Ok(1)), Ok(1));
+    }
+
+    #[test]
+    fn values_expire_on_the_ok_ttl() {
+        let mut cache: Slot = None;
+
+        assert_eq!(fetch(&mut cache, 0, Ok(1)), Ok(1));
+        assert_eq!(fetch(&mut cache, 9, Ok(2)), Ok(1));
+        assert_eq!(fetch(&mut cache, 10, Ok(2)), Ok(2));
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum PlanError {
+        NoPath,
+        BadRoom(std::num::ParseIntError),
+    }
+
+    impl From<std::num::ParseIntError> for PlanError {
+        fn from(e: std::num::ParseIntError) -> Self {
+            PlanError::BadRoom(e)
+        }
+    }
+
+    fn plan(
+        cache: &mut Option<Result<u32, PlanError>>,
+        room: &str,
+        cost: Option<u32>,
+    ) -> Result<u32, String> {
+        cache
+            .try_access(
+                |_| true,
+                || {
+                    let room: u32 = room.parse()?;
+                    let cost = cost.ok_or(PlanError::NoPath)?;
+
+                    Ok(room + cost)
+                },
+            )
+            .get()
+            .copied()
+            .map_err(|e| format!("{:?}", e))
+    }
+
+    #[test]
+    fn question_mark_in_the_filler_surfaces_through_get() {
+        let mut cache = None;
+
+        assert_eq!(plan(&mut cache, "10", Some(5)), Ok(15));
+        assert_eq!(plan(&mut cache, "10", None), Err("NoPath".into()));
+        assert!(plan(&mut cache, "w1n1", Some(5))
+            .unwrap_err()
+            .starts_with("BadRoom"));
+        assert!(matches!(cache, Some(Err(PlanError::BadRoom(_)))));
+    }
+}
@@ -0,0 +1,145 @@
+use super::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+pub struct MtimeStamped<T> {
+    pub value: T,
+    pub mtime: Option<SystemTime>,
+}
+
+impl<T> MtimeStamped<T> {
+    pub fn new(value: T, mtime: Option<SystemTime>) -> Self {
+        MtimeStamped { value, mtime }
+    }
+}
+
+pub struct FileMtimeExpiration {
+    path: PathBuf,
+}
+
+impl FileMtimeExpiration {
+    pub fn new<P>(path: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        FileMtimeExpiration { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // A missing or unreadable file reports `None`, so a file that appears or
+    // disappears also counts as a change.
+    pub fn current_mtime(&self) -> Option<SystemTime> {
+        fs::metadata(&self.path).and_then(|m| m.modified()).ok()
+    }
+
+    pub fn is_expired<T>(&self, value: &MtimeStamped<T>) -> bool {
+        value.mtime != self.current_mtime()
+    }
+
+    pub fn fill<T, F>(&self, filler: F) -> MtimeStamped<T>
+    where
+        F: FnOnce(&Path) -> T,
+    {
+        // Reading the mtime first means a write racing with the fill is seen
+        // as a change on the next access rather than being missed.
+        let mtime = self.current_mtime();
+
+        MtimeStamped::new(filler(&self.path), mtime)
+    }
+}
+
+pub trait FastCacheFileMtimeAccessor<'a, T, R>: FastCacheAccessor<'a, MtimeStamped<T>, R> {
+    #[allow(clippy::type_complexity)]
+    fn access_file_mtime<'p, F>(
+        self,
+        expiration: &'p FileMtimeExpiration,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        MtimeStamped<T>,
+        Self,
+        impl FnOnce(&MtimeStamped<T>) -> bool + 'p,
+        impl FnOnce() -> MtimeStamped<T> + 'p,
+        R,
+    >
+    where
+        F: FnOnce(&Path) -> T + 'p;
+}
+
+impl<'a, C, T, R> FastCacheFileMtimeAccessor<'a, T, R> for C
+where
+    C: FastCacheAccessor<'a, MtimeStamped<T>, R>,
+{
+    #[allow(clippy::type_complexity)]
+    fn access_file_mtime<'p, F>(
+        self,
+        expiration: &'p FileMtimeExpiration,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        MtimeStamped<T>,
+        Self,
+        impl FnOnce(&MtimeStamped<T>) -> bool + 'p,
+        impl FnOnce() -> MtimeStamped<T> + 'p,
+        R,
+    >
+    where
+        F: FnOnce(&Path) -> T + 'p,
+    {
+        self.access(
+            move |v: &MtimeStamped<T>| expiration.is_expired(v),
+            move || expiration.fill(filler),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn read(cache: &mut Option<MtimeStamped<String>>, expiration: &FileMtimeExpiration) -> String {
+        cache
+            .access_file_mtime(expiration, |path| {
+                fs::read_to_string(path).unwrap_or_default()
+            })
+            .take()
+            .value
+            .clone()
+    }
+
+    fn touch(path: &Path, contents: &str, secs: u64) {
+        fs::write(path, contents).unwrap();
+
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+            .unwrap();
+    }
+
+    #[test]
+    fn refills_when_the_file_changes() {
+        let path = std::env::temp_dir().join(format!("screeps-cache-mtime-{}", std::process::id()));
+        let expiration = FileMtimeExpiration::new(&path);
+        let mut cache = None;
+
+        touch(&path, "first", 1_000);
+        assert_eq!(read(&mut cache, &expiration), "first");
+
+        touch(&path, "unseen", 1_000);
+        assert_eq!(read(&mut cache, &expiration), "first");
+
+        touch(&path, "second", 2_000);
+        assert_eq!(read(&mut cache, &expiration), "second");
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(read(&mut cache, &expiration), "");
+        assert_eq!(cache.as_ref().and_then(|v| v.mtime), None);
+    }
+}
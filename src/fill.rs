@@ -0,0 +1,47 @@
+pub fn fill_choice<T, F1, F2>(predicate: bool, cheap: F1, precise: F2) -> impl FnOnce() -> T
+where
+    F1: FnOnce() -> T,
+    F2: FnOnce() -> T,
+{
+    move || if predicate { cheap() } else { precise() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn only_the_selected_filler_runs() {
+        let cheap_runs = Cell::new(0);
+        let precise_runs = Cell::new(0);
+
+        let cheap = || {
+            cheap_runs.set(cheap_runs.get() + 1);
+            1
+        };
+        let precise = || {
+            precise_runs.set(precise_runs.get() + 1);
+            2
+        };
+
+        let mut cache = None;
+
+        let mut accessor = (&mut cache).access(|_| false, fill_choice(true, cheap, precise));
+
+        assert_eq!((cheap_runs.get(), precise_runs.get()), (0, 0));
+        assert_eq!(**accessor.get(), 1);
+        assert_eq!((cheap_runs.get(), precise_runs.get()), (1, 0));
+
+        let mut cache = None;
+
+        assert_eq!(
+            *(&mut cache)
+                .access(|_| false, fill_choice(false, cheap, precise))
+                .take(),
+            2
+        );
+        assert_eq!((cheap_runs.get(), precise_runs.get()), (1, 1));
+    }
+}
@@ -0,0 +1,116 @@
+use super::*;
+
+pub struct FillWatch {
+    last_fill: Cell<Option<u32>>,
+    on_double_fill: Box<dyn Fn(u32)>,
+}
+
+impl FillWatch {
+    pub fn new<H>(on_double_fill: H) -> Self
+    where
+        H: Fn(u32) + 'static,
+    {
+        FillWatch {
+            last_fill: Cell::new(None),
+            on_double_fill: Box::new(on_double_fill),
+        }
+    }
+
+    pub fn last_fill(&self) -> Option<u32> {
+        self.last_fill.get()
+    }
+
+    pub fn record_fill(&self, current_tick: u32) {
+        let previous = self.last_fill.replace(Some(current_tick));
+
+        if cfg!(debug_assertions) && previous == Some(current_tick) {
+            (self.on_double_fill)(current_tick);
+        }
+    }
+}
+
+pub trait FastCacheWatchedAccessor<'a, T, R>: FastCacheAccessor<'a, T, R> {
+    fn watched_access<'w, X, F>(
+        self,
+        watch: &'w FillWatch,
+        current_tick: u32,
+        expiration: X,
+        filler: F,
+    ) -> CacheAccesor<'a, T, Self, X, impl FnOnce() -> T + 'w, R>
+    where
+        F: FnOnce() -> T + 'w,
+        X: FnOnce(&T) -> bool;
+}
+
+impl<'a, C, T, R> FastCacheWatchedAccessor<'a, T, R> for C
+where
+    C: FastCacheAccessor<'a, T, R>,
+{
+    fn watched_access<'w, X, F>(
+        self,
+        watch: &'w FillWatch,
+        current_tick: u32,
+        expiration: X,
+        filler: F,
+    ) -> CacheAccesor<'a, T, Self, X, impl FnOnce() -> T + 'w, R>
+    where
+        F: FnOnce() -> T + 'w,
+        X: FnOnce(&T) -> bool,
+    {
+        self.access(expiration, move || {
+            watch.record_fill(current_tick);
+
+            filler()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    fn counting_watch() -> (FillWatch, Rc<Cell<u32>>) {
+        let warnings = Rc::new(Cell::new(0));
+        let count = warnings.clone();
+
+        (
+            FillWatch::new(move |_| count.set(count.get() + 1)),
+            warnings,
+        )
+    }
+
+    #[test]
+    fn warns_on_a_second_fill_in_one_tick() {
+        let (watch, warnings) = counting_watch();
+        let mut cache = None;
+
+        (&mut cache)
+            .watched_access(&watch, 3, |_| true, || 1)
+            .take();
+        (&mut cache)
+            .watched_access(&watch, 3, |_| true, || 2)
+            .take();
+
+        assert_eq!(watch.last_fill(), Some(3));
+        assert_eq!(warnings.get(), if cfg!(debug_assertions) { 1 } else { 0 });
+    }
+
+    #[test]
+    fn fills_on_separate_ticks_are_quiet() {
+        let (watch, warnings) = counting_watch();
+        let mut cache = None;
+
+        (&mut cache)
+            .watched_access(&watch, 1, |_| true, || 1)
+            .take();
+        (&mut cache)
+            .watched_access(&watch, 2, |_| true, || 2)
+            .take();
+        (&mut cache)
+            .watched_access(&watch, 2, |_| false, || 3)
+            .take();
+
+        assert_eq!(warnings.get(), 0);
+    }
+}
@@ -0,0 +1,198 @@
+use super::*;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CacheGraphError<K> {
+    UnknownNode(K),
+    Cycle(K),
+}
+
+struct GraphNode<K, V> {
+    dependencies: Vec<K>,
+    fill: NodeFill<V>,
+}
+
+type NodeFill<V> = Box<dyn Fn(&[&V]) -> V>;
+
+pub struct CacheGraph<K, V> {
+    nodes: HashMap<K, GraphNode<K, V>>,
+    values: CacheMap<K, V>,
+}
+
+impl<K, V> Default for CacheGraph<K, V> {
+    fn default() -> Self {
+        CacheGraph {
+            nodes: HashMap::new(),
+            values: CacheMap::default(),
+        }
+    }
+}
+
+impl<K, V> CacheGraph<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn node<D, F>(mut self, key: K, dependencies: D, filler: F) -> Self
+    where
+        D: IntoIterator<Item = K>,
+        F: Fn(&[&V]) -> V + 'static,
+    {
+        self.nodes.insert(
+            key,
+            GraphNode {
+                dependencies: dependencies.into_iter().collect(),
+                fill: Box::new(filler),
+            },
+        );
+
+        self
+    }
+
+    pub fn is_filled(&self, key: &K) -> bool {
+        self.values.contains_key(key)
+    }
+
+    pub fn get(&self, key: &K) -> Result<Ref<'_, V>, CacheGraphError<K>> {
+        self.force(key, &mut HashSet::new())?;
+
+        Ok(self.values.get(key).unwrap())
+    }
+
+    fn force(&self, key: &K, visiting: &mut HashSet<K>) -> Result<(), CacheGraphError<K>> {
+        if self.values.contains_key(key) {
+            return Ok(());
+        }
+
+        let node = self
+            .nodes
+            .get(key)
+            .ok_or_else(|| CacheGraphError::UnknownNode(key.clone()))?;
+
+        if !visiting.insert(key.clone()) {
+            return Err(CacheGraphError::Cycle(key.clone()));
+        }
+
+        for dependency in &node.dependencies {
+            self.force(dependency, visiting)?;
+        }
+
+        visiting.remove(key);
+
+        let value = {
+            let dependencies: Vec<Ref<'_, V>> = node
+                .dependencies
+                .iter()
+                .filter_map(|d| self.values.get(d))
+                .collect();
+
+            let dependencies: Vec<&V> = dependencies.iter().map(|d| &**d).collect();
+
+            (node.fill)(&dependencies)
+        };
+
+        self.values.insert(key.clone(), value);
+
+        Ok(())
+    }
+
+    pub fn invalidate(&self, key: &K) {
+        if self.values.remove(key).is_none() {
+            return;
+        }
+
+        for (dependent, node) in &self.nodes {
+            if node.dependencies.contains(key) {
+                self.invalidate(dependent);
+            }
+        }
+    }
+
+    pub fn clear(&self) {
+        self.values.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    fn logged(
+        log: &Rc<RefCell<Vec<&'static str>>>,
+        name: &'static str,
+        own: u32,
+    ) -> impl Fn(&[&u32]) -> u32 + 'static {
+        let log = log.clone();
+
+        move |deps: &[&u32]| {
+            log.borrow_mut().push(name);
+
+            own + deps.iter().copied().sum::<u32>()
+        }
+    }
+
+    #[test]
+    fn fills_dependencies_first_and_each_node_once() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        let graph = CacheGraph::new()
+            .node("total", ["spawn", "towers"], logged(&log, "total", 0))
+            .node("spawn", ["energy"], logged(&log, "spawn", 1))
+            .node("towers", ["energy"], logged(&log, "towers", 2))
+            .node("energy", [], logged(&log, "energy", 10));
+
+        assert_eq!(*graph.get(&"total").unwrap(), 23);
+
+        let order = log.take();
+        let position = |name| order.iter().position(|n| *n == name).unwrap();
+
+        assert_eq!(order.len(), 4);
+        assert_eq!(position("energy"), 0);
+        assert!(position("spawn") < position("total"));
+        assert!(position("towers") < position("total"));
+
+        assert_eq!(*graph.get(&"total").unwrap(), 23);
+        assert!(log.take().is_empty());
+    }
+
+    #[test]
+    fn invalidation_refills_dependents_only() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        let graph = CacheGraph::new()
+            .node("a", [], logged(&log, "a", 1))
+            .node("b", ["a"], logged(&log, "b", 1))
+            .node("c", [], logged(&log, "c", 1));
+
+        graph.get(&"b").unwrap();
+        graph.get(&"c").unwrap();
+        log.take();
+
+        graph.invalidate(&"a");
+        assert!(!graph.is_filled(&"b"));
+        assert!(graph.is_filled(&"c"));
+
+        assert_eq!(*graph.get(&"b").unwrap(), 2);
+        assert_eq!(log.take(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn reports_cycles_and_unknown_nodes() {
+        let graph = CacheGraph::new()
+            .node(1, [2], |_: &[&u32]| 0)
+            .node(2, [1], |_: &[&u32]| 0)
+            .node(3, [4], |_: &[&u32]| 0);
+
+        assert!(matches!(graph.get(&1), Err(CacheGraphError::Cycle(1))));
+        assert!(matches!(
+            graph.get(&3),
+            Err(CacheGraphError::UnknownNode(4))
+        ));
+        assert!(!graph.is_filled(&1));
+    }
+}
@@ -0,0 +1,133 @@
+use super::*;
+use std::sync::{Mutex, MutexGuard};
+
+pub struct LockedGuard<'a, T>(MutexGuard<'a, Option<T>>);
+
+impl<'a, T> Deref for LockedGuard<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match &*self.0 {
+            Some(data) => data,
+            None => unreachable!("locked guards are only built over filled slots"),
+        }
+    }
+}
+
+pub enum CacheGuard<'a, T> {
+    Borrowed(&'a T),
+    Ref(Ref<'a, T>),
+    Locked(LockedGuard<'a, T>),
+}
+
+impl<'a, T> CacheGuard<'a, T> {
+    pub fn locked(guard: MutexGuard<'a, Option<T>>) -> Option<Self> {
+        if guard.is_some() {
+            Some(CacheGuard::Locked(LockedGuard(guard)))
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T> Deref for CacheGuard<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self {
+            CacheGuard::Borrowed(data) => data,
+            CacheGuard::Ref(data) => data,
+            CacheGuard::Locked(data) => data,
+        }
+    }
+}
+
+impl<'a, T> From<&'a T> for CacheGuard<'a, T> {
+    fn from(data: &'a T) -> Self {
+        CacheGuard::Borrowed(data)
+    }
+}
+
+impl<'a, T> From<Ref<'a, T>> for CacheGuard<'a, T> {
+    fn from(data: Ref<'a, T>) -> Self {
+        CacheGuard::Ref(data)
+    }
+}
+
+impl<'c, T, C, X, F, R> CacheAccesor<'c, T, C, X, F, R>
+where
+    F: FnOnce() -> T,
+    X: FnOnce(&T) -> bool,
+    C: FastCacheGet<'c, T, R> + FastCacheExpiration<T>,
+{
+    pub fn into_guard(self) -> CacheGuard<'c, T>
+    where
+        R: Into<CacheGuard<'c, T>>,
+    {
+        self.take().into()
+    }
+}
+
+pub trait FastCacheLockedAccessor<'a, T> {
+    fn access_locked<X, F>(self, expiration: X, filler: F) -> CacheGuard<'a, T>
+    where
+        X: FnOnce(&T) -> bool,
+        F: FnOnce() -> T;
+}
+
+impl<'a, T> FastCacheLockedAccessor<'a, T> for &'a Mutex<Option<T>> {
+    fn access_locked<X, F>(self, expiration: X, filler: F) -> CacheGuard<'a, T>
+    where
+        X: FnOnce(&T) -> bool,
+        F: FnOnce() -> T,
+    {
+        let mut guard = self.lock().unwrap();
+
+        (&mut *guard).expire_with(expiration);
+        guard.get_or_insert_with(move || cold_fill(filler));
+
+        CacheGuard::Locked(LockedGuard(guard))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doubled<'a>(guard: impl Into<CacheGuard<'a, u32>>) -> u32 {
+        *guard.into() * 2
+    }
+
+    #[test]
+    fn generic_code_reads_every_backend() {
+        let mut option = None;
+        let cell = RefCell::new(None);
+
+        assert_eq!(
+            doubled((&mut option).access(|_| false, || 2).into_guard()),
+            4
+        );
+        assert_eq!(doubled((&cell).access(|_| false, || 3).into_guard()), 6);
+    }
+
+    #[test]
+    fn locked_guard_holds_the_mutex() {
+        let mutex = Mutex::new(Some(1));
+
+        let guard = (&mutex).access_locked(|v| *v == 1, || 5);
+
+        assert_eq!(*guard, 5);
+        assert!(mutex.try_lock().is_err());
+
+        drop(guard);
+
+        assert_eq!(*mutex.lock().unwrap(), Some(5));
+    }
+
+    #[test]
+    fn locked_rejects_empty_slots() {
+        let mutex: Mutex<Option<u32>> = Mutex::new(None);
+
+        assert!(CacheGuard::locked(mutex.lock().unwrap()).is_none());
+    }
+}
@@ -0,0 +1,138 @@
+use super::*;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+pub struct InternedKeyCacheMap<V> {
+    entries: RefCell<HashMap<Rc<str>, V>>,
+    interned: RefCell<HashSet<Rc<str>>>,
+}
+
+impl<V> Default for InternedKeyCacheMap<V> {
+    fn default() -> Self {
+        InternedKeyCacheMap {
+            entries: RefCell::new(HashMap::new()),
+            interned: RefCell::new(HashSet::new()),
+        }
+    }
+}
+
+impl<V> InternedKeyCacheMap<V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.borrow().is_empty()
+    }
+
+    pub fn interned_len(&self) -> usize {
+        self.interned.borrow().len()
+    }
+
+    pub fn intern(&self, key: &str) -> Rc<str> {
+        let mut interned = self.interned.borrow_mut();
+
+        match interned.get(key) {
+            Some(existing) => existing.clone(),
+            None => {
+                let key: Rc<str> = Rc::from(key);
+
+                interned.insert(key.clone());
+
+                key
+            }
+        }
+    }
+
+    pub fn key(&self, key: &str) -> Option<Rc<str>> {
+        self.entries
+            .borrow()
+            .get_key_value(key)
+            .map(|(k, _)| k.clone())
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.entries.borrow().contains_key(key)
+    }
+
+    pub fn get(&self, key: &str) -> Option<Ref<'_, V>> {
+        Ref::filter_map(self.entries.borrow(), |e| e.get(key)).ok()
+    }
+
+    pub fn insert(&self, key: &str, value: V) -> Option<V> {
+        let key = self.intern(key);
+
+        self.entries.borrow_mut().insert(key, value)
+    }
+
+    pub fn remove(&self, key: &str) -> Option<V> {
+        self.entries.borrow_mut().remove(key)
+    }
+
+    pub fn clear(&self) {
+        self.entries.borrow_mut().clear();
+    }
+
+    pub fn prune_interned(&self) {
+        let entries = self.entries.borrow();
+
+        self.interned
+            .borrow_mut()
+            .retain(|k| entries.contains_key(k));
+    }
+
+    pub fn get_or_insert_with<F>(&self, key: &str, filler: F) -> Ref<'_, V>
+    where
+        F: FnOnce() -> V,
+    {
+        if !self.entries.borrow().contains_key(key) {
+            let value = filler();
+
+            self.insert(key, value);
+        }
+
+        Ref::map(self.entries.borrow(), |e| &e[key])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_keys_share_one_allocation() {
+        let map = InternedKeyCacheMap::new();
+        let first = String::from("W1N1");
+        let second = String::from("W1N1");
+
+        map.insert(&first, 1);
+        let key = map.key("W1N1").unwrap();
+
+        map.remove(&first);
+        map.insert(&second, 2);
+
+        assert!(Rc::ptr_eq(&key, &map.key("W1N1").unwrap()));
+        assert!(Rc::ptr_eq(&key, &map.intern(&second)));
+        assert_eq!(map.interned_len(), 1);
+        assert_eq!(*map.get("W1N1").unwrap(), 2);
+    }
+
+    #[test]
+    fn prune_interned_drops_unused_keys() {
+        let map = InternedKeyCacheMap::new();
+
+        assert_eq!(*map.get_or_insert_with("a", || 1), 1);
+        assert_eq!(*map.get_or_insert_with("a", || 2), 1);
+        map.insert("b", 3);
+        map.remove("b");
+        assert_eq!(map.interned_len(), 2);
+
+        map.prune_interned();
+        assert_eq!(map.interned_len(), 1);
+        assert!(map.contains_key("a"));
+    }
+}
@@ -0,0 +1,142 @@
+use super::*;
+
+pub struct TtlJitter {
+    base_ttl: u32,
+    jitter_range: u32,
+    state: Cell<u64>,
+}
+
+impl TtlJitter {
+    pub fn new(base_ttl: u32, jitter_range: u32, seed: u64) -> Self {
+        TtlJitter {
+            base_ttl,
+            jitter_range,
+            state: Cell::new(seed),
+        }
+    }
+
+    pub fn base_ttl(&self) -> u32 {
+        self.base_ttl
+    }
+
+    pub fn jitter_range(&self) -> u32 {
+        self.jitter_range
+    }
+
+    fn next_random(&self) -> u64 {
+        // SplitMix64 keeps the sequence deterministic for a given seed
+        // without pulling in an RNG dependency.
+        let state = self.state.get().wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        self.state.set(state);
+
+        let mut z = state;
+
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+
+        z ^ (z >> 31)
+    }
+
+    pub fn next_ttl(&self) -> u32 {
+        let jitter = self.next_random() % (self.jitter_range as u64 + 1);
+
+        self.base_ttl.saturating_add(jitter as u32)
+    }
+}
+
+pub trait FastCacheJitterAccessor<'a, T, R>: FastCacheAccessor<'a, ValidUntil<T>, R> {
+    #[allow(clippy::type_complexity)]
+    fn access_with_ttl_jitter<'j, F>(
+        self,
+        jitter: &'j TtlJitter,
+        current_tick: u32,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        ValidUntil<T>,
+        Self,
+        impl FnOnce(&ValidUntil<T>) -> bool,
+        impl FnOnce() -> ValidUntil<T> + 'j,
+        R,
+    >
+    where
+        F: FnOnce() -> T + 'j;
+}
+
+impl<'a, C, T, R> FastCacheJitterAccessor<'a, T, R> for C
+where
+    C: FastCacheAccessor<'a, ValidUntil<T>, R>,
+{
+    #[allow(clippy::type_complexity)]
+    fn access_with_ttl_jitter<'j, F>(
+        self,
+        jitter: &'j TtlJitter,
+        current_tick: u32,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        ValidUntil<T>,
+        Self,
+        impl FnOnce(&ValidUntil<T>) -> bool,
+        impl FnOnce() -> ValidUntil<T> + 'j,
+        R,
+    >
+    where
+        F: FnOnce() -> T + 'j,
+    {
+        self.access(
+            move |v: &ValidUntil<T>| !v.is_valid_at(current_tick),
+            move || {
+                let valid_until = current_tick.saturating_add(jitter.next_ttl());
+
+                ValidUntil::new(filler(), valid_until)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ttls_fall_within_the_jittered_range() {
+        let jitter = TtlJitter::new(100, 20, 42);
+
+        let ttls: Vec<u32> = (0..200).map(|_| jitter.next_ttl()).collect();
+
+        assert!(ttls.iter().all(|ttl| (100..=120).contains(ttl)));
+        assert!(ttls.iter().any(|ttl| *ttl != ttls[0]));
+    }
+
+    #[test]
+    fn the_same_seed_gives_the_same_sequence() {
+        let a = TtlJitter::new(10, 5, 7);
+        let b = TtlJitter::new(10, 5, 7);
+
+        let first: Vec<u32> = (0..16).map(|_| a.next_ttl()).collect();
+        let second: Vec<u32> = (0..16).map(|_| b.next_ttl()).collect();
+
+        assert_eq!(first, second);
+        assert_eq!(TtlJitter::new(10, 0, 7).next_ttl(), 10);
+    }
+
+    #[test]
+    fn caches_filled_together_expire_on_different_ticks() {
+        let jitter = TtlJitter::new(50, 30, 1);
+        let mut caches: Vec<Option<ValidUntil<u32>>> = (0..8).map(|_| None).collect();
+
+        for cache in caches.iter_mut() {
+            cache.access_with_ttl_jitter(&jitter, 1000, || 1).take();
+        }
+
+        let expiries: Vec<u32> = caches
+            .iter()
+            .map(|c| c.as_ref().unwrap().valid_until)
+            .collect();
+
+        assert!(expiries.iter().all(|t| (1050..=1080).contains(t)));
+        assert!(expiries.iter().any(|t| *t != expiries[0]));
+    }
+}
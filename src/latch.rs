@@ -0,0 +1,71 @@
+use super::*;
+
+#[derive(Default)]
+pub struct OnceLatch {
+    latch: Option<()>,
+}
+
+impl OnceLatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_latched(&self) -> bool {
+        self.latch.is_some()
+    }
+
+    pub fn reset(&mut self) {
+        self.latch = None;
+    }
+
+    pub fn run_once<A>(&mut self, action: A)
+    where
+        A: FnOnce(),
+    {
+        self.run_once_with(|| false, action);
+    }
+
+    pub fn run_once_with<X, A>(&mut self, expiration: X, action: A)
+    where
+        X: FnOnce() -> bool,
+        A: FnOnce(),
+    {
+        (&mut self.latch).access(|_| expiration(), action).take();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_once_only_runs_until_reset() {
+        let mut latch = OnceLatch::new();
+        let runs = Cell::new(0);
+
+        assert!(!latch.is_latched());
+
+        latch.run_once(|| runs.set(runs.get() + 1));
+        latch.run_once(|| runs.set(runs.get() + 1));
+        assert!(latch.is_latched());
+        assert_eq!(runs.get(), 1);
+
+        latch.reset();
+        assert!(!latch.is_latched());
+
+        latch.run_once(|| runs.set(runs.get() + 1));
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[test]
+    fn run_once_with_clears_the_latch_on_expiration() {
+        let mut latch = OnceLatch::new();
+        let runs = Cell::new(0);
+
+        for tick in 0..6 {
+            latch.run_once_with(|| tick % 3 == 0, || runs.set(runs.get() + 1));
+        }
+
+        assert_eq!(runs.get(), 2);
+    }
+}
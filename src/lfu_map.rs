@@ -0,0 +1,258 @@
+use super::*;
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+struct LfuEntry<V> {
+    value: V,
+    count: Cell<u32>,
+    seq: u64,
+}
+
+pub struct LfuCacheMap<K, V> {
+    entries: RefCell<HashMap<K, LfuEntry<V>>>,
+    frequencies: RefCell<BTreeMap<(u32, u64), K>>,
+    next_seq: Cell<u64>,
+    capacity: usize,
+    accesses: Cell<u32>,
+    decay_interval: Cell<Option<u32>>,
+}
+
+impl<K, V> LfuCacheMap<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "lfu cache capacity must be non-zero");
+
+        LfuCacheMap {
+            entries: RefCell::new(HashMap::new()),
+            frequencies: RefCell::new(BTreeMap::new()),
+            next_seq: Cell::new(0),
+            capacity,
+            accesses: Cell::new(0),
+            decay_interval: Cell::new(None),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn set_decay_interval(&self, accesses: Option<u32>) {
+        self.decay_interval.set(accesses);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.borrow().is_empty()
+    }
+
+    pub fn frequency<Q>(&self, key: &Q) -> Option<u32>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.entries.borrow().get(key).map(|e| e.count.get())
+    }
+
+    pub fn decay(&self) {
+        let mut frequencies = self.frequencies.borrow_mut();
+
+        for (key, entry) in self.entries.borrow().iter() {
+            let count = entry.count.get();
+
+            frequencies.remove(&(count, entry.seq));
+            frequencies.insert((count / 2, entry.seq), key.clone());
+
+            entry.count.set(count / 2);
+        }
+    }
+
+    fn record_access(&self) {
+        let interval = match self.decay_interval.get() {
+            Some(interval) if interval > 0 => interval,
+            _ => return,
+        };
+
+        let accesses = self.accesses.get().wrapping_add(1);
+
+        self.accesses.set(accesses);
+
+        if accesses.is_multiple_of(interval) {
+            self.decay();
+        }
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<Ref<'_, V>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let entry = Ref::filter_map(self.entries.borrow(), |e| e.get(key)).ok()?;
+
+        let count = entry.count.get();
+        let mut frequencies = self.frequencies.borrow_mut();
+
+        if let Some(key) = frequencies.remove(&(count, entry.seq)) {
+            frequencies.insert((count.saturating_add(1), entry.seq), key);
+        }
+
+        entry.count.set(count.saturating_add(1));
+
+        drop(frequencies);
+        drop(entry);
+
+        self.record_access();
+
+        Some(Ref::map(self.entries.borrow(), |e| &e[key].value))
+    }
+
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        let previous = self.remove(&key);
+
+        let mut entries = self.entries.borrow_mut();
+        let mut frequencies = self.frequencies.borrow_mut();
+
+        while entries.len() >= self.capacity {
+            match frequencies.pop_first() {
+                Some((_, lfu)) => entries.remove(&lfu),
+                None => break,
+            };
+        }
+
+        let seq = self.next_seq.get();
+
+        self.next_seq.set(seq + 1);
+
+        let entry = LfuEntry {
+            value,
+            count: Cell::new(1),
+            seq,
+        };
+
+        frequencies.insert((1, seq), key.clone());
+        entries.insert(key, entry);
+
+        previous
+    }
+
+    pub fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let entry = self.entries.borrow_mut().remove(key)?;
+
+        self.frequencies
+            .borrow_mut()
+            .remove(&(entry.count.get(), entry.seq));
+
+        Some(entry.value)
+    }
+
+    pub fn clear(&self) {
+        self.entries.borrow_mut().clear();
+        self.frequencies.borrow_mut().clear();
+    }
+
+    pub fn get_or_insert_with<F>(&self, key: K, filler: F) -> Ref<'_, V>
+    where
+        F: FnOnce() -> V,
+    {
+        if self.get(&key).is_none() {
+            let value = filler();
+
+            self.insert(key.clone(), value);
+        }
+
+        Ref::map(self.entries.borrow(), |e| &e[&key].value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_the_least_frequently_used_key() {
+        let map = LfuCacheMap::new(2);
+
+        map.insert(1, "hot");
+        map.insert(2, "cold");
+        map.get(&1);
+        map.get(&1);
+        map.insert(3, "new");
+
+        assert_eq!(map.frequency(&1), Some(3));
+        assert_eq!(map.frequency(&2), None);
+        assert_eq!(map.frequency(&3), Some(1));
+    }
+
+    #[test]
+    fn ties_evict_the_oldest_entry() {
+        let map = LfuCacheMap::new(2);
+
+        map.insert(1, 1);
+        map.insert(2, 2);
+        map.insert(3, 3);
+
+        assert!(map.get(&1).is_none());
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn decay_lets_stale_entries_be_evicted() {
+        let map = LfuCacheMap::new(2);
+
+        map.insert(1, "once popular");
+
+        for _ in 0..4 {
+            map.get(&1);
+        }
+
+        map.decay();
+        map.decay();
+
+        map.insert(2, "steady");
+        map.get(&2);
+        map.get(&2);
+
+        assert_eq!(map.frequency(&1), Some(1));
+        assert_eq!(map.frequency(&2), Some(3));
+
+        map.insert(3, "new");
+
+        assert_eq!(map.frequency(&1), None);
+        assert!(map.get(&2).is_some());
+    }
+
+    #[test]
+    fn decays_on_the_configured_interval() {
+        let map = LfuCacheMap::new(2);
+
+        map.insert(1, 1);
+        map.set_decay_interval(Some(2));
+
+        map.get(&1);
+        assert_eq!(map.frequency(&1), Some(2));
+
+        map.get(&1);
+        assert_eq!(map.frequency(&1), Some(1));
+    }
+
+    #[test]
+    fn reinserting_resets_the_count() {
+        let map = LfuCacheMap::new(1);
+
+        map.insert(1, 1);
+        map.get(&1);
+
+        assert_eq!(map.insert(1, 2), Some(1));
+        assert_eq!(map.frequency(&1), Some(1));
+        assert_eq!(map.len(), 1);
+    }
+}
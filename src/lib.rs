@@ -2,6 +2,150 @@ use std::cell::*;
 use std::marker::PhantomData;
 use std::ops::*;
 
+mod adapt;
+mod adaptive;
+mod any;
+mod arc;
+mod arena_map;
+#[cfg(feature = "bench-support")]
+mod bench_support;
+mod bounded_map;
+mod boxed;
+mod cached;
+#[cfg(feature = "catch-unwind")]
+mod catch_panic;
+mod chain;
+mod conditional;
+mod copy_cache;
+#[cfg(feature = "dashmap")]
+mod dash_map;
+mod deadline;
+#[cfg(feature = "serde")]
+mod delta_map;
+mod detach;
+mod drop_order;
+mod either;
+mod epoch;
+mod expirable;
+mod fallible;
+mod file_mtime;
+mod fill;
+mod fill_watch;
+mod graph;
+mod guard;
+mod interned_map;
+mod jitter;
+mod latch;
+mod lfu_map;
+mod located;
+mod map;
+mod metrics;
+mod nested_map;
+mod once;
+mod ordered_map;
+mod outcome;
+mod owned;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod pinned;
+mod policy;
+mod poll;
+mod record;
+mod recycle;
+mod reusable;
+mod schedule;
+#[cfg(feature = "serde")]
+mod serialize;
+mod shared;
+mod skip_fill;
+mod slice;
+mod sliding;
+mod slots;
+#[cfg(feature = "smallbox")]
+mod small_boxed;
+mod small_int_map;
+#[cfg(feature = "serde")]
+mod snapshot;
+mod split;
+mod staleness;
+mod stateful;
+mod sync_map;
+mod tick;
+mod tick_scope;
+#[cfg(feature = "tracing")]
+mod traced;
+mod try_get;
+mod watch;
+
+pub use adapt::*;
+pub use adaptive::*;
+pub use any::*;
+pub use arc::*;
+pub use arena_map::*;
+#[cfg(feature = "bench-support")]
+pub use bench_support::*;
+pub use bounded_map::*;
+pub use boxed::*;
+pub use cached::*;
+#[cfg(feature = "catch-unwind")]
+pub use catch_panic::*;
+pub use chain::*;
+pub use conditional::*;
+pub use copy_cache::*;
+#[cfg(feature = "dashmap")]
+pub use dash_map::*;
+pub use deadline::*;
+#[cfg(feature = "serde")]
+pub use delta_map::*;
+pub use drop_order::*;
+pub use either::*;
+pub use epoch::*;
+pub use expirable::*;
+pub use fallible::*;
+pub use file_mtime::*;
+pub use fill::*;
+pub use fill_watch::*;
+pub use graph::*;
+pub use guard::*;
+pub use interned_map::*;
+pub use jitter::*;
+pub use latch::*;
+pub use lfu_map::*;
+pub use located::*;
+pub use map::*;
+pub use metrics::*;
+pub use nested_map::*;
+pub use once::*;
+pub use ordered_map::*;
+pub use outcome::*;
+pub use owned::*;
+#[cfg(feature = "rayon")]
+pub use parallel::*;
+pub use pinned::*;
+pub use policy::*;
+pub use poll::*;
+pub use record::*;
+pub use recycle::*;
+pub use reusable::*;
+pub use schedule::*;
+pub use shared::*;
+pub use skip_fill::*;
+pub use sliding::*;
+pub use slots::*;
+#[cfg(feature = "smallbox")]
+pub use small_boxed::*;
+pub use small_int_map::*;
+#[cfg(feature = "serde")]
+pub use snapshot::*;
+pub use split::*;
+pub use staleness::*;
+pub use stateful::*;
+pub use sync_map::*;
+pub use tick::*;
+pub use tick_scope::*;
+pub use try_get::*;
+pub use watch::*;
+
 pub trait FastCacheExpiration<T> {
     fn expire_with<X>(self, expiration: X) -> Self
     where
@@ -16,10 +160,17 @@ pub trait FastCacheMaybeGet<'a, T, R> {
     fn maybe_get_or_insert_with<F: FnOnce() -> Option<T>>(self, f: F) -> Option<R>;
 }
 
+// A maybe-backend that hands itself back when the fill produced nothing, so
+// that a fallback can still be stored into it afterwards.
+pub trait FastCacheMaybeRetain<'a, T, R>: FastCacheMaybeGet<'a, T, R> + Sized {
+    fn maybe_get_or_retain<F: FnOnce() -> Option<T>>(self, f: F) -> Result<R, Self>;
+}
+
 //
 // Option
 //
 
+#[allow(clippy::needless_lifetimes)]
 impl<'a, T> FastCacheExpiration<T> for &'a mut Option<T> {
     fn expire_with<X>(self, expiration: X) -> Self
     where
@@ -49,10 +200,24 @@ impl<'a, T> FastCacheMaybeGet<'a, T, &'a T> for &'a mut Option<T> {
     }
 }
 
+impl<'a, T> FastCacheMaybeRetain<'a, T, &'a T> for &'a mut Option<T> {
+    fn maybe_get_or_retain<F: FnOnce() -> Option<T>>(self, f: F) -> Result<&'a T, Self> {
+        if self.is_none() {
+            *self = (f)();
+        }
+
+        match *self {
+            Some(ref value) => Ok(value),
+            None => Err(self),
+        }
+    }
+}
+
 //
 // Refcell
 //
 
+#[allow(clippy::needless_lifetimes)]
 impl<'a, T> FastCacheExpiration<T> for &'a RefCell<Option<T>> {
     fn expire_with<X>(self, expiration: X) -> Self
     where
@@ -90,6 +255,12 @@ impl<'a, T> FastCacheMaybeGet<'a, T, Ref<'a, T>> for &'a RefCell<Option<T>> {
     }
 }
 
+impl<'a, T> FastCacheMaybeRetain<'a, T, Ref<'a, T>> for &'a RefCell<Option<T>> {
+    fn maybe_get_or_retain<F: FnOnce() -> Option<T>>(self, f: F) -> Result<Ref<'a, T>, Self> {
+        self.maybe_get_or_insert_with(f).ok_or(self)
+    }
+}
+
 //
 // Implementation
 //
@@ -105,7 +276,7 @@ where
 }
 
 pub trait FastCacheMaybeAccessor<'a, T, R>:
-    FastCacheExpiration<T> + FastCacheMaybeGet<'a, T, R>
+    FastCacheExpiration<T> + FastCacheMaybeRetain<'a, T, R>
 where
     Self: Sized,
 {
@@ -145,7 +316,7 @@ where
 
 impl<'a, C, T, R> FastCacheMaybeAccessor<'a, T, R> for C
 where
-    C: FastCacheExpiration<T> + FastCacheMaybeGet<'a, T, R>,
+    C: FastCacheExpiration<T> + FastCacheMaybeRetain<'a, T, R>,
 {
     fn maybe_access<X, F>(self, expiration: X, filler: F) -> MaybeCacheAccesor<'a, T, Self, X, F, R>
     where
@@ -167,6 +338,145 @@ where
     }
 }
 
+pub trait FastCacheRetryAccessor<'a, T, R>: FastCacheMaybeAccessor<'a, T, R> {
+    #[allow(clippy::type_complexity)]
+    fn maybe_access_retry<X, F>(
+        self,
+        expiration: X,
+        attempts: usize,
+        filler: F,
+    ) -> MaybeCacheAccesor<'a, T, Self, X, impl FnOnce() -> Option<T>, R>
+    where
+        F: FnMut() -> Option<T>,
+        X: FnOnce(&T) -> bool;
+}
+
+impl<'a, C, T, R> FastCacheRetryAccessor<'a, T, R> for C
+where
+    C: FastCacheMaybeAccessor<'a, T, R>,
+{
+    #[allow(clippy::type_complexity)]
+    fn maybe_access_retry<X, F>(
+        self,
+        expiration: X,
+        attempts: usize,
+        mut filler: F,
+    ) -> MaybeCacheAccesor<'a, T, Self, X, impl FnOnce() -> Option<T>, R>
+    where
+        F: FnMut() -> Option<T>,
+        X: FnOnce(&T) -> bool,
+    {
+        self.maybe_access(expiration, move || (0..attempts).find_map(|_| filler()))
+    }
+}
+
+pub trait FastCacheRetryUntilAccessor<'a, T, R>: FastCacheAccessor<'a, T, R> {
+    // Calls `filler` until `accept` approves a value or `attempts` runs out,
+    // storing the last value produced. The filler always runs at least once.
+    #[allow(clippy::type_complexity)]
+    fn access_retry<X, V, F>(
+        self,
+        expiration: X,
+        attempts: usize,
+        accept: V,
+        filler: F,
+    ) -> CacheAccesor<'a, T, Self, X, impl FnOnce() -> T, R>
+    where
+        F: FnMut() -> T,
+        V: FnMut(&T) -> bool,
+        X: FnOnce(&T) -> bool;
+}
+
+impl<'a, C, T, R> FastCacheRetryUntilAccessor<'a, T, R> for C
+where
+    C: FastCacheAccessor<'a, T, R>,
+{
+    #[allow(clippy::type_complexity)]
+    fn access_retry<X, V, F>(
+        self,
+        expiration: X,
+        attempts: usize,
+        mut accept: V,
+        mut filler: F,
+    ) -> CacheAccesor<'a, T, Self, X, impl FnOnce() -> T, R>
+    where
+        F: FnMut() -> T,
+        V: FnMut(&T) -> bool,
+        X: FnOnce(&T) -> bool,
+    {
+        self.access(expiration, move || {
+            let mut value = filler();
+
+            for _ in 1..attempts {
+                if accept(&value) {
+                    break;
+                }
+
+                value = filler();
+            }
+
+            value
+        })
+    }
+}
+
+pub trait Finite {
+    fn is_finite(&self) -> bool;
+}
+
+impl Finite for f32 {
+    fn is_finite(&self) -> bool {
+        f32::is_finite(*self)
+    }
+}
+
+impl Finite for f64 {
+    fn is_finite(&self) -> bool {
+        f64::is_finite(*self)
+    }
+}
+
+pub trait FastCacheFiniteAccessor<'a, T, R>: FastCacheAccessor<'a, T, R>
+where
+    T: Finite,
+{
+    fn access_finite<X, F>(
+        self,
+        expiration: X,
+        filler: F,
+    ) -> CacheAccesor<'a, T, Self, impl FnOnce(&T) -> bool, F, R>
+    where
+        F: FnOnce() -> T,
+        X: FnOnce(&T) -> bool;
+}
+
+impl<'a, C, T, R> FastCacheFiniteAccessor<'a, T, R> for C
+where
+    C: FastCacheAccessor<'a, T, R>,
+    T: Finite,
+{
+    fn access_finite<X, F>(
+        self,
+        expiration: X,
+        filler: F,
+    ) -> CacheAccesor<'a, T, Self, impl FnOnce(&T) -> bool, F, R>
+    where
+        F: FnOnce() -> T,
+        X: FnOnce(&T) -> bool,
+    {
+        self.access(move |v: &T| !v.is_finite() || expiration(v), filler)
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn cold_fill<T, F>(fill: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    fill()
+}
+
 pub trait Get<R> {
     fn get(&mut self) -> &R;
 
@@ -206,6 +516,20 @@ where
     }
 }
 
+impl<'c, T, C, X, F, R> CacheAccesor<'c, T, C, X, F, R>
+where
+    F: FnOnce() -> T,
+    X: FnOnce(&T) -> bool,
+    C: FastCacheGet<'c, T, R> + FastCacheExpiration<T>,
+{
+    pub fn copy_out(&mut self) -> R
+    where
+        R: Copy,
+    {
+        *self.get()
+    }
+}
+
 pub enum CacheState<'c, T, C, X, F, R>
 where
     F: FnOnce() -> T,
@@ -225,10 +549,12 @@ where
     pub fn into_known(self) -> Self {
         match self {
             CacheState::Unknown(state, _) => {
+                let fill = state.fill;
+
                 let ref_val = state
                     .cache
                     .expire_with(state.expiration)
-                    .get_or_insert_with(state.fill);
+                    .get_or_insert_with(move || cold_fill(fill));
 
                 let new_state = CacheStateKnown { data: ref_val };
 
@@ -265,7 +591,7 @@ pub struct MaybeCacheAccesor<'c, T, C, X, F, R>
 where
     F: FnOnce() -> Option<T>,
     X: FnOnce(&T) -> bool,
-    C: FastCacheMaybeGet<'c, T, R> + FastCacheExpiration<T>,
+    C: FastCacheMaybeRetain<'c, T, R> + FastCacheExpiration<T>,
 {
     state: MaybeCacheState<'c, T, C, X, F, R>,
     phantom: PhantomData<R>,
@@ -275,21 +601,57 @@ impl<'c, T, C, X, F, R> MaybeGet<R> for MaybeCacheAccesor<'c, T, C, X, F, R>
 where
     F: FnOnce() -> Option<T>,
     X: FnOnce(&T) -> bool,
-    C: FastCacheMaybeGet<'c, T, R> + FastCacheExpiration<T>,
+    C: FastCacheMaybeRetain<'c, T, R> + FastCacheExpiration<T>,
 {
     fn get(&mut self) -> Option<&R> {
         take_mut::take(&mut self.state, |v| v.into_known());
 
         match &self.state {
             MaybeCacheState::Unknown(_, _) => unsafe { std::hint::unreachable_unchecked() },
-            MaybeCacheState::Known(s) => s.data.as_ref(),
+            MaybeCacheState::Known(s) => Some(&s.data),
+            MaybeCacheState::Empty(_) => None,
         }
     }
 
     fn take(self) -> Option<R> {
         match self.state.into_known() {
             MaybeCacheState::Unknown(_, _) => unsafe { std::hint::unreachable_unchecked() },
+            MaybeCacheState::Known(s) => Some(s.data),
+            MaybeCacheState::Empty(_) => None,
+        }
+    }
+}
+
+impl<'c, T, C, X, F, R> MaybeCacheAccesor<'c, T, C, X, F, R>
+where
+    F: FnOnce() -> Option<T>,
+    X: FnOnce(&T) -> bool,
+    C: FastCacheMaybeRetain<'c, T, R> + FastCacheExpiration<T>,
+{
+    pub fn copy_out(&mut self) -> Option<R>
+    where
+        R: Copy,
+    {
+        self.get().copied()
+    }
+
+    pub fn or_insert(self, fallback: T) -> R
+    where
+        C: FastCacheGet<'c, T, R>,
+    {
+        match self.state {
+            MaybeCacheState::Unknown(state, _) => {
+                let fill = state.fill;
+
+                state
+                    .cache
+                    .expire_with(state.expiration)
+                    .get_or_insert_with(move || cold_fill(move || fill().unwrap_or(fallback)))
+            }
             MaybeCacheState::Known(s) => s.data,
+            MaybeCacheState::Empty(cache) => {
+                cache.get_or_insert_with(move || cold_fill(move || fallback))
+            }
         }
     }
 }
@@ -298,29 +660,35 @@ pub enum MaybeCacheState<'c, T, C, X, F, R>
 where
     F: FnOnce() -> Option<T>,
     X: FnOnce(&T) -> bool,
-    C: FastCacheMaybeGet<'c, T, R> + FastCacheExpiration<T>,
+    C: FastCacheMaybeRetain<'c, T, R> + FastCacheExpiration<T>,
 {
     Unknown(MaybeCacheStateUnknown<'c, T, C, X, F, R>, PhantomData<R>),
     Known(MaybeCacheStateKnown<R>),
+    // The fill produced nothing; the backend is kept so that a fallback can
+    // still be stored by `or_insert`.
+    Empty(C),
 }
 
 impl<'c, T, C, X, F, R> MaybeCacheState<'c, T, C, X, F, R>
 where
     F: FnOnce() -> Option<T>,
     X: FnOnce(&T) -> bool,
-    C: FastCacheMaybeGet<'c, T, R> + FastCacheExpiration<T>,
+    C: FastCacheMaybeRetain<'c, T, R> + FastCacheExpiration<T>,
 {
     pub fn into_known(self) -> Self {
         match self {
             MaybeCacheState::Unknown(state, _) => {
+                let fill = state.fill;
+
                 let ref_val = state
                     .cache
                     .expire_with(state.expiration)
-                    .maybe_get_or_insert_with(state.fill);
-
-                let new_state = MaybeCacheStateKnown { data: ref_val };
+                    .maybe_get_or_retain(move || cold_fill(fill));
 
-                MaybeCacheState::Known(new_state)
+                match ref_val {
+                    Ok(data) => MaybeCacheState::Known(MaybeCacheStateKnown { data }),
+                    Err(cache) => MaybeCacheState::Empty(cache),
+                }
             }
             v => v,
         }
@@ -331,7 +699,7 @@ pub struct MaybeCacheStateUnknown<'c, T, C, X, F, R>
 where
     X: FnOnce(&T) -> bool,
     F: FnOnce() -> Option<T>,
-    C: FastCacheMaybeGet<'c, T, R> + FastCacheExpiration<T>,
+    C: FastCacheMaybeRetain<'c, T, R> + FastCacheExpiration<T>,
 {
     cache: C,
     expiration: X,
@@ -340,5 +708,255 @@ where
 }
 
 pub struct MaybeCacheStateKnown<T> {
-    data: Option<T>,
+    data: T,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn or_insert_persists_the_fallback() {
+        let mut cache: Option<u32> = None;
+
+        assert_eq!(
+            *(&mut cache).maybe_access(|_| false, || None).or_insert(4),
+            4
+        );
+        assert_eq!(cache, Some(4));
+
+        let value = (&mut cache)
+            .maybe_access(|_| false, || Some(9))
+            .or_insert(5);
+
+        assert_eq!(*value, 4);
+    }
+
+    #[test]
+    fn or_insert_prefers_a_successful_fill() {
+        let cache = RefCell::new(None);
+
+        assert_eq!(
+            *(&cache).maybe_access(|_| false, || Some(2)).or_insert(5),
+            2
+        );
+        assert_eq!(*cache.borrow(), Some(2));
+    }
+
+    #[test]
+    fn access_finite_retries_nan_fills() {
+        let mut cache: Option<f64> = None;
+        let fills = Cell::new(0);
+
+        let fill = || {
+            fills.set(fills.get() + 1);
+            if fills.get() == 1 {
+                f64::NAN
+            } else {
+                1.5
+            }
+        };
+
+        assert!((&mut cache).access_finite(|_| false, fill).take().is_nan());
+        assert_eq!(*(&mut cache).access_finite(|_| false, fill).take(), 1.5);
+        assert_eq!(*(&mut cache).access_finite(|_| false, fill).take(), 1.5);
+        assert_eq!(fills.get(), 2);
+    }
+
+    #[test]
+    fn access_finite_keeps_the_caller_expiration() {
+        let mut cache = Some(2.0f32);
+
+        assert_eq!(
+            *(&mut cache).access_finite(|v| *v > 1.0, || 0.5).take(),
+            0.5
+        );
+    }
+
+    #[test]
+    fn retry_calls_an_fnmut_filler_until_it_succeeds() {
+        let mut cache: Option<u32> = None;
+        let mut attempts = 0;
+
+        let value = (&mut cache)
+            .maybe_access_retry(
+                |_| false,
+                5,
+                || {
+                    attempts += 1;
+                    if attempts == 3 {
+                        Some(attempts)
+                    } else {
+                        None
+                    }
+                },
+            )
+            .take()
+            .copied();
+
+        assert_eq!(value, Some(3));
+        assert_eq!(attempts, 3);
+    }
+
+    #[test]
+    fn retry_gives_up_after_the_attempt_limit() {
+        let cache: RefCell<Option<u32>> = RefCell::new(None);
+        let mut attempts = 0;
+
+        let value = (&cache)
+            .maybe_access_retry(
+                |_| false,
+                2,
+                || {
+                    attempts += 1;
+                    None
+                },
+            )
+            .take();
+
+        assert!(value.is_none());
+        assert_eq!(attempts, 2);
+        assert!(cache.borrow().is_none());
+    }
+
+    #[test]
+    fn copy_out_reads_repeatedly_from_one_accessor() {
+        let mut cache = None;
+        let fills = Cell::new(0);
+
+        let mut accessor = (&mut cache).access(
+            |_| false,
+            || {
+                fills.set(fills.get() + 1);
+                7u32
+            },
+        );
+
+        let first: &u32 = accessor.copy_out();
+        let second: &u32 = accessor.copy_out();
+
+        assert_eq!((*first, *second), (7, 7));
+        assert_eq!(fills.get(), 1);
+    }
+
+    #[test]
+    fn maybe_copy_out_reads_repeatedly() {
+        let mut cache = None;
+
+        let mut accessor = (&mut cache).maybe_access(|_| false, || Some(3u32));
+
+        assert_eq!(accessor.copy_out().copied(), Some(3));
+        assert_eq!(accessor.copy_out().copied(), Some(3));
+    }
+
+    #[test]
+    fn hit_path_never_reaches_the_cold_fill() {
+        let mut cache = None;
+        let fills = Cell::new(0);
+
+        let value = **(&mut cache)
+            .access(
+                |_| false,
+                || {
+                    fills.set(fills.get() + 1);
+                    9
+                },
+            )
+            .get();
+
+        assert_eq!(value, 9);
+        assert_eq!(fills.get(), 1);
+
+        for _ in 0..3 {
+            let mut accessor = (&mut cache).access(|_| false, || -> u32 { panic!("hit filled") });
+            assert_eq!(**accessor.get(), 9);
+
+            let filled = (&mut cache)
+                .maybe_access(|_| false, || -> Option<u32> { panic!("hit filled") })
+                .take()
+                .copied();
+            assert_eq!(filled, Some(9));
+        }
+
+        let shared = RefCell::new(Some(4));
+
+        for _ in 0..3 {
+            let value = *(&shared)
+                .access(|_| false, || -> u32 { panic!("hit filled") })
+                .take();
+            assert_eq!(value, 4);
+        }
+    }
+
+    #[test]
+    fn or_insert_after_an_empty_get() {
+        let mut cache: Option<u32> = None;
+
+        let mut accessor = (&mut cache).maybe_access(|_| false, || None);
+
+        assert!(accessor.get().is_none());
+        assert_eq!(*accessor.or_insert(6), 6);
+        assert_eq!(cache, Some(6));
+    }
+
+    #[test]
+    fn maybe_get_implementors_need_only_the_fill_method() {
+        struct Slot(Option<u32>);
+
+        impl<'a> FastCacheMaybeGet<'a, u32, u32> for &'a mut Slot {
+            fn maybe_get_or_insert_with<F: FnOnce() -> Option<u32>>(self, f: F) -> Option<u32> {
+                (&mut self.0).maybe_get_or_insert_with(f).copied()
+            }
+        }
+
+        let mut slot = Slot(None);
+
+        assert_eq!((&mut slot).maybe_get_or_insert_with(|| None), None);
+        assert_eq!((&mut slot).maybe_get_or_insert_with(|| Some(3)), Some(3));
+        assert_eq!((&mut slot).maybe_get_or_insert_with(|| Some(4)), Some(3));
+    }
+
+    #[test]
+    fn access_retry_refills_until_a_value_is_accepted() {
+        let mut cache: Option<u32> = None;
+        let mut attempts = 0;
+
+        let value = *(&mut cache)
+            .access_retry(
+                |_| false,
+                5,
+                |v| *v >= 3,
+                || {
+                    attempts += 1;
+                    attempts
+                },
+            )
+            .take();
+
+        assert_eq!(value, 3);
+        assert_eq!(attempts, 3);
+        assert_eq!(cache, Some(3));
+    }
+
+    #[test]
+    fn access_retry_keeps_the_last_value_when_attempts_run_out() {
+        let cache: RefCell<Option<u32>> = RefCell::new(None);
+        let mut attempts = 0;
+
+        let value = *(&cache)
+            .access_retry(
+                |_| false,
+                2,
+                |_| false,
+                || {
+                    attempts += 1;
+                    attempts * 10
+                },
+            )
+            .take();
+
+        assert_eq!(value, 20);
+        assert_eq!(attempts, 2);
+        assert_eq!(*cache.borrow(), Some(20));
+    }
 }
@@ -0,0 +1,193 @@
+use super::*;
+use std::panic::Location;
+
+pub struct Located<T> {
+    pub value: T,
+    location: Option<&'static Location<'static>>,
+}
+
+impl<T> Located<T> {
+    #[track_caller]
+    pub fn new(value: T) -> Self {
+        let location = if cfg!(feature = "track-caller") {
+            Some(Location::caller())
+        } else {
+            None
+        };
+
+        Located { value, location }
+    }
+
+    pub fn fill_location(&self) -> Option<&'static Location<'static>> {
+        self.location
+    }
+}
+
+pub struct LocatedCacheAccesor<'c, T, C, X, F, R>
+where
+    F: FnOnce() -> T,
+    X: FnOnce(&T) -> bool,
+    C: FastCacheGet<'c, Located<T>, R> + FastCacheExpiration<Located<T>>,
+{
+    state: LocatedState<C, X, F, R>,
+    phantom: PhantomData<(&'c C, T)>,
+}
+
+pub enum LocatedState<C, X, F, R> {
+    Unknown { cache: C, expiration: X, fill: F },
+    Known(R),
+}
+
+impl<C, X, F, R> LocatedState<C, X, F, R> {
+    pub fn into_known<'c, T>(self, location: Option<&'static Location<'static>>) -> Self
+    where
+        F: FnOnce() -> T,
+        X: FnOnce(&T) -> bool,
+        C: FastCacheGet<'c, Located<T>, R> + FastCacheExpiration<Located<T>>,
+    {
+        match self {
+            LocatedState::Unknown {
+                cache,
+                expiration,
+                fill,
+            } => {
+                let cache = cache.expire_with(move |v: &Located<T>| expiration(&v.value));
+
+                LocatedState::Known(cache.get_or_insert_with(move || {
+                    cold_fill(move || Located {
+                        value: fill(),
+                        location,
+                    })
+                }))
+            }
+            v => v,
+        }
+    }
+}
+
+impl<'c, T, C, X, F, R> LocatedCacheAccesor<'c, T, C, X, F, R>
+where
+    F: FnOnce() -> T,
+    X: FnOnce(&T) -> bool,
+    C: FastCacheGet<'c, Located<T>, R> + FastCacheExpiration<Located<T>>,
+    R: Deref<Target = Located<T>>,
+{
+    pub fn fill_location(&self) -> Option<&'static Location<'static>> {
+        match &self.state {
+            LocatedState::Known(data) => data.fill_location(),
+            LocatedState::Unknown { .. } => None,
+        }
+    }
+}
+
+impl<'c, T, C, X, F, R> Get<R> for LocatedCacheAccesor<'c, T, C, X, F, R>
+where
+    F: FnOnce() -> T,
+    X: FnOnce(&T) -> bool,
+    C: FastCacheGet<'c, Located<T>, R> + FastCacheExpiration<Located<T>>,
+{
+    #[track_caller]
+    fn get(&mut self) -> &R {
+        let location = if cfg!(feature = "track-caller") {
+            Some(Location::caller())
+        } else {
+            None
+        };
+
+        take_mut::take(&mut self.state, |v| v.into_known(location));
+
+        match &self.state {
+            LocatedState::Unknown { .. } => unreachable!(),
+            LocatedState::Known(data) => data,
+        }
+    }
+
+    #[track_caller]
+    fn take(self) -> R {
+        let location = if cfg!(feature = "track-caller") {
+            Some(Location::caller())
+        } else {
+            None
+        };
+
+        match self.state.into_known(location) {
+            LocatedState::Unknown { .. } => unreachable!(),
+            LocatedState::Known(data) => data,
+        }
+    }
+}
+
+pub trait FastCacheLocatedAccessor<'a, T, R>: FastCacheAccessor<'a, Located<T>, R> {
+    fn access_located<X, F>(
+        self,
+        expiration: X,
+        filler: F,
+    ) -> LocatedCacheAccesor<'a, T, Self, X, F, R>
+    where
+        F: FnOnce() -> T,
+        X: FnOnce(&T) -> bool;
+}
+
+impl<'a, C, T, R> FastCacheLocatedAccessor<'a, T, R> for C
+where
+    C: FastCacheAccessor<'a, Located<T>, R>,
+{
+    fn access_located<X, F>(
+        self,
+        expiration: X,
+        filler: F,
+    ) -> LocatedCacheAccesor<'a, T, Self, X, F, R>
+    where
+        F: FnOnce() -> T,
+        X: FnOnce(&T) -> bool,
+    {
+        LocatedCacheAccesor {
+            state: LocatedState::Unknown {
+                cache: self,
+                expiration,
+                fill: filler,
+            },
+            phantom: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_where_the_fill_was_forced() {
+        let mut cache = None;
+
+        let mut accessor = (&mut cache).access_located(|_| false, || 1);
+
+        assert!(accessor.fill_location().is_none());
+
+        let line = line!() + 1;
+        assert_eq!(accessor.get().value, 1);
+
+        let location = accessor.fill_location();
+
+        assert_eq!(location.is_some(), cfg!(feature = "track-caller"));
+
+        if let Some(location) = location {
+            assert_eq!(location.file(), file!());
+            assert_eq!(location.line(), line);
+        }
+    }
+
+    #[test]
+    fn hits_keep_the_original_location() {
+        let mut cache = None;
+
+        let first = (&mut cache)
+            .access_located(|_| false, || 1)
+            .take()
+            .fill_location();
+        let second = (&mut cache).access_located(|_| false, || 2).take();
+
+        assert_eq!(second.value, 1);
+        assert_eq!(second.fill_location(), first);
+    }
+}
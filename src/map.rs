@@ -0,0 +1,1281 @@
+use super::*;
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Watermark {
+    High,
+    Low,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CacheEvent<'e, K, V> {
+    Inserted(&'e K, &'e V),
+    Replaced(&'e K, &'e V, &'e V),
+    Removed(&'e K, &'e V),
+    Evicted(&'e K, &'e V),
+}
+
+struct Watermarks {
+    low: usize,
+    high: usize,
+    on_cross: Box<dyn FnMut(Watermark)>,
+}
+
+pub struct CacheMap<K, V, S = RandomState> {
+    entries: RefCell<HashMap<K, V, S>>,
+    auto_reserve: Cell<Option<f32>>,
+    watermarks: RefCell<Option<Watermarks>>,
+    weigher: RefCell<Option<Weigher<V>>>,
+    total_weight: Cell<u64>,
+    observer: RefCell<Option<Observer<K, V>>>,
+    absences: RefCell<HashMap<K, u32, S>>,
+}
+
+type Weigher<V> = Box<dyn Fn(&V) -> u64>;
+
+type Observer<K, V> = Box<dyn FnMut(CacheEvent<'_, K, V>)>;
+
+impl<K, V, S> Default for CacheMap<K, V, S>
+where
+    S: Default,
+{
+    fn default() -> Self {
+        CacheMap {
+            entries: RefCell::new(HashMap::default()),
+            auto_reserve: Cell::new(None),
+            watermarks: RefCell::new(None),
+            weigher: RefCell::new(None),
+            total_weight: Cell::new(0),
+            observer: RefCell::new(None),
+            absences: RefCell::new(HashMap::default()),
+        }
+    }
+}
+
+impl<K, V> CacheMap<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<K, V, S> CacheMap<K, V, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher,
+{
+    pub fn with_hasher(hash_builder: S) -> Self
+    where
+        S: Clone,
+    {
+        let absences = HashMap::with_hasher(hash_builder.clone());
+
+        CacheMap {
+            entries: RefCell::new(HashMap::with_hasher(hash_builder)),
+            auto_reserve: Cell::new(None),
+            watermarks: RefCell::new(None),
+            weigher: RefCell::new(None),
+            total_weight: Cell::new(0),
+            observer: RefCell::new(None),
+            absences: RefCell::new(absences),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.entries.borrow().capacity()
+    }
+
+    pub fn reserve(&self, additional: usize) {
+        self.entries.borrow_mut().reserve(additional);
+    }
+
+    pub fn set_auto_reserve(&self, load_factor: Option<f32>) {
+        self.auto_reserve.set(load_factor);
+    }
+
+    pub fn set_watermarks<W>(&self, low: usize, high: usize, on_cross: W)
+    where
+        W: FnMut(Watermark) + 'static,
+    {
+        *self.watermarks.borrow_mut() = Some(Watermarks {
+            low,
+            high,
+            on_cross: Box::new(on_cross),
+        });
+    }
+
+    pub fn clear_watermarks(&self) {
+        self.watermarks.borrow_mut().take();
+    }
+
+    pub fn set_weigher<W>(&self, weigh: W)
+    where
+        W: Fn(&V) -> u64 + 'static,
+    {
+        *self.weigher.borrow_mut() = Some(Box::new(weigh));
+
+        self.recompute_weight();
+    }
+
+    // Installs `observer` to be told about inserts, replacements, removals
+    // and evictions. Changes the observer makes to the map from inside the
+    // callback are applied but not reported back to it.
+    pub fn set_observer<O>(&self, observer: O)
+    where
+        O: FnMut(CacheEvent<'_, K, V>) + 'static,
+    {
+        *self.observer.borrow_mut() = Some(Box::new(observer));
+    }
+
+    pub fn clear_observer(&self) {
+        self.observer.borrow_mut().take();
+    }
+
+    fn has_observer(&self) -> bool {
+        self.observer.borrow().is_some()
+    }
+
+    fn notify(&self, event: CacheEvent<'_, K, V>) {
+        // Events raised while the observer is running, such as by an observer
+        // that mutates the map, are dropped rather than queued. Insert events
+        // are raised while the map is borrowed for reading.
+        if let Ok(mut observer) = self.observer.try_borrow_mut() {
+            if let Some(observer) = observer.as_mut() {
+                observer(event);
+            }
+        }
+    }
+
+    fn notify_evicted(&self, evicted: &[(K, V)]) {
+        for (key, value) in evicted {
+            self.notify(CacheEvent::Evicted(key, value));
+        }
+    }
+
+    pub fn total_weight(&self) -> u64 {
+        self.total_weight.get()
+    }
+
+    fn weigh(&self, value: &V) -> u64 {
+        self.weigher
+            .borrow()
+            .as_ref()
+            .map(|w| w(value))
+            .unwrap_or(0)
+    }
+
+    fn recompute_weight(&self) {
+        let total = match &*self.weigher.borrow() {
+            Some(weigh) => self
+                .entries
+                .borrow()
+                .values()
+                .fold(0u64, |total, v| total.saturating_add(weigh(v))),
+            None => 0,
+        };
+
+        self.total_weight.set(total);
+    }
+
+    fn sub_weight(&self, removed: u64) {
+        let total = self.total_weight.get().saturating_sub(removed);
+
+        self.total_weight.set(total);
+    }
+
+    fn notify_watermarks(&self, previous_len: usize, len: usize) {
+        let crossed = match &*self.watermarks.borrow() {
+            Some(w) if previous_len < w.high && len >= w.high => Watermark::High,
+            Some(w) if previous_len > w.low && len <= w.low => Watermark::Low,
+            _ => return,
+        };
+
+        let watermarks = self.watermarks.borrow_mut().take();
+
+        if let Some(mut watermarks) = watermarks {
+            (watermarks.on_cross)(crossed);
+
+            let mut slot = self.watermarks.borrow_mut();
+
+            if slot.is_none() {
+                *slot = Some(watermarks);
+            }
+        }
+    }
+
+    // The total weight once `value` is stored under `key`, or `None` if it
+    // would not fit in a u64.
+    fn weight_after(&self, key: &K, value: &V) -> Option<u64> {
+        let added = self.weigh(value);
+
+        let replaced = match self.entries.borrow().get(key) {
+            Some(previous) => self.weigh(previous),
+            None => 0,
+        };
+
+        self.total_weight
+            .get()
+            .saturating_sub(replaced)
+            .checked_add(added)
+    }
+
+    fn put(&self, key: K, value: V) -> Option<V>
+    where
+        K: Clone,
+    {
+        let total = self.weight_after(&key, &value).unwrap_or(u64::MAX);
+
+        self.put_weighed(key, value, total)
+    }
+
+    fn put_weighed(&self, key: K, value: V, total: u64) -> Option<V>
+    where
+        K: Clone,
+    {
+        self.forget_absence(&key);
+
+        let observed = if self.has_observer() {
+            Some(key.clone())
+        } else {
+            None
+        };
+
+        let mut entries = self.entries.borrow_mut();
+
+        let previous = entries.insert(key, value);
+        let len = entries.len();
+
+        if let Some(load_factor) = self.auto_reserve.get() {
+            if len as f32 >= entries.capacity() as f32 * load_factor {
+                entries.reserve(len);
+            }
+        }
+
+        drop(entries);
+
+        self.total_weight.set(total);
+
+        if let Some(key) = observed {
+            let entries = self.entries.borrow();
+            let value = &entries[&key];
+
+            match &previous {
+                Some(previous) => self.notify(CacheEvent::Replaced(&key, previous, value)),
+                None => self.notify(CacheEvent::Inserted(&key, value)),
+            }
+        }
+
+        previous
+    }
+
+    // Infallible stores saturate the total weight rather than failing;
+    // `try_insert` is the path that reports an overflow.
+    fn store(&self, key: K, value: V) -> Option<V>
+    where
+        K: Clone,
+    {
+        let previous_len = self.len();
+        let previous = self.put(key, value);
+
+        self.notify_watermarks(previous_len, self.len());
+
+        previous
+    }
+
+    fn forget_absence<Q>(&self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let mut absences = self.absences.borrow_mut();
+
+        if !absences.is_empty() {
+            absences.remove(key);
+        }
+    }
+
+    fn drain_entries(&self) {
+        if self.has_observer() {
+            let evicted: Vec<_> = self.entries.borrow_mut().drain().collect();
+
+            self.notify_evicted(&evicted);
+        } else {
+            self.entries.borrow_mut().clear();
+        }
+
+        self.total_weight.set(0);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.borrow().is_empty()
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.entries.borrow().contains_key(key)
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<Ref<'_, V>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        Ref::filter_map(self.entries.borrow(), |e| e.get(key)).ok()
+    }
+
+    pub fn insert(&self, key: K, value: V) -> Option<V>
+    where
+        K: Clone,
+    {
+        self.store(key, value)
+    }
+
+    pub fn try_insert(&self, key: K, value: V) -> Result<Option<V>, CacheError>
+    where
+        K: Clone,
+    {
+        let total = self
+            .weight_after(&key, &value)
+            .ok_or(CacheError::WeightOverflow)?;
+
+        let previous_len = self.len();
+        let previous = self.put_weighed(key, value, total);
+
+        self.notify_watermarks(previous_len, self.len());
+
+        Ok(previous)
+    }
+
+    pub fn remove_entry<Q>(&self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let mut entries = self.entries.borrow_mut();
+
+        let previous_len = entries.len();
+        let removed = entries.remove_entry(key);
+        let len = entries.len();
+
+        drop(entries);
+
+        if let Some((key, value)) = &removed {
+            self.forget_absence::<K>(key);
+            self.sub_weight(self.weigh(value));
+            self.notify(CacheEvent::Removed(key, value));
+        }
+
+        self.notify_watermarks(previous_len, len);
+
+        removed
+    }
+
+    pub fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    pub fn clear(&self) {
+        let previous_len = self.len();
+
+        self.absences.borrow_mut().clear();
+        self.drain_entries();
+        self.notify_watermarks(previous_len, 0);
+    }
+
+    pub fn extract_if<P>(&self, mut pred: P) -> Vec<(K, V)>
+    where
+        P: FnMut(&K, &V) -> bool,
+    {
+        let extracted: Vec<_> = self
+            .entries
+            .borrow_mut()
+            .extract_if(|k, v| pred(k, v))
+            .collect();
+
+        let removed = extracted
+            .iter()
+            .fold(0u64, |total, (_, v)| total.saturating_add(self.weigh(v)));
+
+        for (key, _) in &extracted {
+            self.forget_absence(key);
+        }
+
+        self.sub_weight(removed);
+        self.notify_evicted(&extracted);
+
+        extracted
+    }
+
+    pub fn decay<F, P>(&self, mut f: F, mut drop_if: P) -> Vec<(K, V)>
+    where
+        F: FnMut(&mut V),
+        P: FnMut(&V) -> bool,
+    {
+        let extracted: Vec<_> = self
+            .entries
+            .borrow_mut()
+            .extract_if(|_, v| {
+                f(v);
+
+                drop_if(v)
+            })
+            .collect();
+
+        for (key, _) in &extracted {
+            self.forget_absence(key);
+        }
+
+        self.recompute_weight();
+        self.notify_evicted(&extracted);
+
+        extracted
+    }
+
+    pub fn merge<T, P>(&self, other: CacheMap<K, V, T>, resolve: P)
+    where
+        K: Clone,
+        P: Fn(&V, V) -> V,
+    {
+        let other = other.entries.into_inner();
+        let previous_len = self.len();
+
+        self.reserve(other.len());
+
+        for (key, value) in other {
+            // The resolver runs with the existing value out of the map, so it
+            // may read the map; the value goes back before the store so that
+            // the collision is reported as a single replacement.
+            let existing = self.entries.borrow_mut().remove(&key);
+
+            let value = match existing {
+                Some(existing) => {
+                    let value = resolve(&existing, value);
+
+                    self.entries.borrow_mut().insert(key.clone(), existing);
+
+                    value
+                }
+                None => value,
+            };
+
+            self.put(key, value);
+        }
+
+        self.notify_watermarks(previous_len, self.len());
+    }
+
+    pub fn get_or_insert_with<F>(&self, key: K, filler: F) -> Ref<'_, V>
+    where
+        K: Clone,
+        F: FnOnce() -> V,
+    {
+        self.get_or_insert_with_tracked(key, filler).0
+    }
+
+    pub fn get_or_insert_many<I, F>(&self, keys: I, mut filler: F) -> Vec<Ref<'_, V>>
+    where
+        K: Clone,
+        I: IntoIterator<Item = K>,
+        F: FnMut(&K) -> V,
+    {
+        let keys: Vec<K> = keys.into_iter().collect();
+
+        for key in &keys {
+            if !self.entries.borrow().contains_key(key) {
+                let value = filler(key);
+
+                self.store(key.clone(), value);
+            }
+        }
+
+        keys.iter()
+            .map(|key| Ref::map(self.entries.borrow(), |e| &e[key]))
+            .collect()
+    }
+
+    pub fn get_or_insert_with_tracked<F>(&self, key: K, filler: F) -> (Ref<'_, V>, bool)
+    where
+        K: Clone,
+        F: FnOnce() -> V,
+    {
+        let filled = !self.entries.borrow().contains_key(&key);
+
+        if filled {
+            let value = filler();
+
+            self.store(key.clone(), value);
+        }
+
+        (Ref::map(self.entries.borrow(), |e| &e[&key]), filled)
+    }
+
+    pub fn maybe_get_or_insert_with<F>(
+        &self,
+        key: K,
+        negative_ttl: u32,
+        current_tick: u32,
+        filler: F,
+    ) -> Option<Ref<'_, V>>
+    where
+        K: Clone,
+        F: FnOnce() -> Option<V>,
+    {
+        if !self.entries.borrow().contains_key(&key) {
+            let absent_since = self.absences.borrow().get(&key).copied();
+
+            if let Some(tick) = absent_since {
+                if current_tick.wrapping_sub(tick) < negative_ttl {
+                    return None;
+                }
+
+                self.forget_absence(&key);
+            }
+
+            match filler() {
+                Some(value) => {
+                    self.store(key.clone(), value);
+                }
+                None => {
+                    self.absences.borrow_mut().insert(key, current_tick);
+
+                    return None;
+                }
+            }
+        }
+
+        Some(Ref::map(self.entries.borrow(), |e| &e[&key]))
+    }
+
+    pub fn expire_absences(&self, negative_ttl: u32, current_tick: u32) {
+        self.absences
+            .borrow_mut()
+            .retain(|_, tick| current_tick.wrapping_sub(*tick) < negative_ttl);
+    }
+
+    pub fn get_or_insert_read_through<T, F>(
+        &self,
+        key: K,
+        source: &CacheMap<K, V, T>,
+        filler: F,
+    ) -> Ref<'_, V>
+    where
+        K: Clone,
+        V: Clone,
+        T: BuildHasher,
+        F: FnOnce() -> V,
+    {
+        if !self.entries.borrow().contains_key(&key) {
+            let shared = source.get(&key).map(|v| v.clone());
+
+            let value = shared.unwrap_or_else(filler);
+
+            self.store(key.clone(), value);
+        }
+
+        Ref::map(self.entries.borrow(), |e| &e[&key])
+    }
+
+    pub fn get_or_insert_with_validated<P, F>(&self, key: K, validate: P, filler: F) -> Ref<'_, V>
+    where
+        K: Clone,
+        P: FnOnce(&V) -> bool,
+        F: FnOnce() -> V,
+    {
+        let valid = self
+            .entries
+            .borrow()
+            .get(&key)
+            .map(validate)
+            .unwrap_or(false);
+
+        if !valid {
+            let value = filler();
+
+            self.store(key.clone(), value);
+        }
+
+        Ref::map(self.entries.borrow(), |e| &e[&key])
+    }
+
+    pub fn get_fresh<F>(&self, key: K, filler: F) -> Ref<'_, V>
+    where
+        K: Clone,
+        F: FnOnce() -> V,
+    {
+        let value = filler();
+
+        self.store(key.clone(), value);
+
+        Ref::map(self.entries.borrow(), |e| &e[&key])
+    }
+
+    pub fn snapshot(&self) -> Vec<(K, V)>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        self.entries
+            .borrow()
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+
+    pub fn restore(&self, snapshot: Vec<(K, V)>)
+    where
+        K: Clone,
+    {
+        let previous_len = self.len();
+
+        self.drain_entries();
+
+        for (key, value) in snapshot {
+            self.put(key, value);
+        }
+
+        self.notify_watermarks(previous_len, self.len());
+    }
+}
+
+impl<K, V, S> CacheMap<K, ValidUntil<V>, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher,
+{
+    pub fn sweep_expired(&self, current_tick: u32) -> Vec<(K, V)> {
+        self.extract_if(|_, v| !v.is_valid_at(current_tick))
+            .into_iter()
+            .map(|(k, v)| (k, v.value))
+            .collect()
+    }
+}
+
+impl<'a, K, R, S> CacheMap<K, BoxedAccessor<'a, R>, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher,
+{
+    pub fn force<Q>(&self, key: &Q) -> Option<Ref<'_, R>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if !self.entries.borrow().get(key)?.is_forced() {
+            self.entries.borrow_mut().get_mut(key)?.get();
+        }
+
+        Ref::filter_map(self.entries.borrow(), |e| e.get(key).and_then(|a| a.peek())).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn tracks_total_weight_across_operations() {
+        let map = CacheMap::new();
+
+        map.set_weigher(|v: &Vec<u8>| v.len() as u64);
+
+        map.insert(1, vec![0; 4]);
+        map.insert(2, vec![0; 6]);
+        assert_eq!(map.total_weight(), 10);
+
+        map.insert(1, vec![0; 1]);
+        assert_eq!(map.total_weight(), 7);
+
+        map.remove(&2);
+        assert_eq!(map.total_weight(), 1);
+
+        map.get_or_insert_with(3, || vec![0; 5]);
+        assert_eq!(map.total_weight(), 6);
+
+        map.clear();
+        assert_eq!(map.total_weight(), 0);
+    }
+
+    #[test]
+    fn rejects_inserts_that_overflow_the_weight() {
+        let map = CacheMap::new();
+
+        map.set_weigher(|v: &u64| *v);
+
+        assert_eq!(map.try_insert(1, u64::MAX - 1), Ok(None));
+        assert_eq!(map.try_insert(2, 2), Err(CacheError::WeightOverflow));
+        assert!(!map.contains_key(&2));
+        assert_eq!(map.total_weight(), u64::MAX - 1);
+
+        assert_eq!(map.try_insert(1, 3), Ok(Some(u64::MAX - 1)));
+        assert_eq!(map.total_weight(), 3);
+    }
+
+    #[test]
+    fn infallible_paths_saturate_the_weight() {
+        let map = CacheMap::new();
+
+        map.set_weigher(|v: &u64| *v);
+
+        map.insert(1, u64::MAX - 1);
+        map.insert(2, 2);
+        assert_eq!(map.total_weight(), u64::MAX);
+
+        map.get_or_insert_with(3, || 5);
+        assert_eq!(map.total_weight(), u64::MAX);
+        assert_eq!(map.len(), 3);
+
+        let other = CacheMap::new();
+        other.insert(4, u64::MAX);
+        map.merge(other, |ours, _| *ours);
+        assert_eq!(map.total_weight(), u64::MAX);
+
+        map.restore(vec![(1, u64::MAX), (2, 1)]);
+        assert_eq!(map.total_weight(), u64::MAX);
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn merge_keeps_the_weight_in_sync() {
+        let map = CacheMap::new();
+        let other = CacheMap::new();
+
+        map.set_weigher(|v: &Vec<u8>| v.len() as u64);
+        map.insert("a", vec![1]);
+        other.insert("a", vec![2, 3]);
+        other.insert("b", vec![4]);
+
+        map.merge(other, |ours, theirs| [ours.as_slice(), &theirs].concat());
+
+        assert_eq!(map.total_weight(), 4);
+    }
+
+    fn record_events(map: &CacheMap<u32, u32>) -> Rc<RefCell<Vec<String>>> {
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let log = events.clone();
+
+        map.set_observer(move |event| {
+            let entry = match event {
+                CacheEvent::Inserted(k, v) => format!("inserted {} {}", k, v),
+                CacheEvent::Replaced(k, old, new) => format!("replaced {} {} {}", k, old, new),
+                CacheEvent::Removed(k, v) => format!("removed {} {}", k, v),
+                CacheEvent::Evicted(k, v) => format!("evicted {} {}", k, v),
+            };
+
+            log.borrow_mut().push(entry);
+        });
+
+        events
+    }
+
+    #[test]
+    fn observes_a_mixed_operation_set() {
+        let map = CacheMap::new();
+        let events = record_events(&map);
+
+        map.insert(1, 10);
+        map.insert(1, 11);
+        map.get_or_insert_with(2, || 20);
+        map.remove(&1);
+        map.clear();
+
+        assert_eq!(
+            events.take(),
+            vec![
+                "inserted 1 10",
+                "replaced 1 10 11",
+                "inserted 2 20",
+                "removed 1 11",
+                "evicted 2 20",
+            ]
+        );
+    }
+
+    #[test]
+    fn observer_sees_the_stored_value() {
+        let map = Rc::new(CacheMap::new());
+        let seen = Rc::new(Cell::new(None));
+
+        let (observed, log) = (Rc::downgrade(&map), seen.clone());
+
+        map.set_observer(move |event| {
+            if let CacheEvent::Inserted(k, _) = event {
+                let map = observed.upgrade().unwrap();
+
+                log.set(map.get(k).map(|v| *v));
+            }
+        });
+
+        map.insert(1, 5);
+
+        assert_eq!(seen.get(), Some(5));
+    }
+
+    #[test]
+    fn merge_and_restore_are_observed() {
+        let map = CacheMap::new();
+        let other = CacheMap::new();
+
+        map.insert(1, 1);
+        other.insert(1, 2);
+        other.insert(2, 3);
+
+        let events = record_events(&map);
+
+        map.merge(other, |a, b| a + b);
+
+        let mut merged = events.take();
+        merged.sort();
+
+        assert_eq!(merged, vec!["inserted 2 3", "replaced 1 1 3"]);
+
+        map.restore(vec![(4, 4)]);
+
+        let mut restored = events.take();
+        restored.sort();
+
+        assert_eq!(restored, vec!["evicted 1 3", "evicted 2 3", "inserted 4 4"]);
+    }
+
+    #[test]
+    fn merge_crosses_watermarks_once() {
+        let map = CacheMap::new();
+        let crossings = Rc::new(RefCell::new(Vec::new()));
+        let log = crossings.clone();
+
+        map.insert(1, 1);
+        map.set_watermarks(0, 2, move |mark| log.borrow_mut().push(mark));
+
+        let other = CacheMap::new();
+
+        other.insert(1, 1);
+        other.insert(2, 2);
+
+        map.merge(other, |a, _| *a);
+        map.clear();
+
+        assert_eq!(crossings.take(), vec![Watermark::High, Watermark::Low]);
+    }
+
+    #[test]
+    fn cached_absence_suppresses_the_filler_until_the_ttl() {
+        let map: CacheMap<u32, u32> = CacheMap::new();
+        let calls = Cell::new(0);
+
+        let resolve = || {
+            calls.set(calls.get() + 1);
+            None
+        };
+
+        assert!(map.maybe_get_or_insert_with(1, 5, 100, resolve).is_none());
+        assert!(map.maybe_get_or_insert_with(1, 5, 104, resolve).is_none());
+        assert_eq!(calls.get(), 1);
+
+        assert!(map.maybe_get_or_insert_with(1, 5, 105, resolve).is_none());
+        assert_eq!(calls.get(), 2);
+
+        let value = map.maybe_get_or_insert_with(1, 5, 110, || Some(7));
+
+        assert_eq!(value.map(|v| *v), Some(7));
+    }
+
+    #[test]
+    fn insert_and_remove_forget_absences() {
+        let map: CacheMap<u32, u32> = CacheMap::new();
+
+        assert!(map.maybe_get_or_insert_with(1, 10, 0, || None).is_none());
+
+        map.insert(1, 3);
+        map.remove(&1);
+
+        let value = map.maybe_get_or_insert_with(1, 10, 1, || Some(4));
+
+        assert_eq!(value.map(|v| *v), Some(4));
+    }
+
+    #[test]
+    fn expired_absences_are_pruned() {
+        let map: CacheMap<u32, u32> = CacheMap::new();
+
+        map.maybe_get_or_insert_with(1, 5, 0, || None);
+        map.maybe_get_or_insert_with(2, 5, 3, || None);
+
+        map.expire_absences(5, 6);
+
+        // Under a longer negative TTL a retained absence would still suppress
+        // the fill, so only the pruned key fills.
+        let pruned = map.maybe_get_or_insert_with(1, 100, 6, || Some(10));
+        assert_eq!(pruned.map(|v| *v), Some(10));
+
+        let retained = map.maybe_get_or_insert_with(2, 100, 6, || Some(20));
+        assert!(retained.is_none());
+    }
+
+    #[test]
+    fn tracked_lookups_report_fills() {
+        let map = CacheMap::new();
+
+        let (value, filled) = map.get_or_insert_with_tracked("a", || 1);
+        assert_eq!((*value, filled), (1, true));
+        drop(value);
+
+        let (value, filled) = map.get_or_insert_with_tracked("a", || 2);
+        assert_eq!((*value, filled), (1, false));
+        drop(value);
+
+        assert_eq!(*map.get_or_insert_with("b", || 3), 3);
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn restores_a_snapshot_after_mutation() {
+        let map = CacheMap::new();
+
+        map.insert(1, "one");
+        map.insert(2, "two");
+
+        let snapshot = map.snapshot();
+
+        map.insert(3, "three");
+        map.insert(1, "uno");
+        map.remove(&2);
+
+        map.restore(snapshot);
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&1).map(|v| *v), Some("one"));
+        assert_eq!(map.get(&2).map(|v| *v), Some("two"));
+        assert!(!map.contains_key(&3));
+    }
+
+    #[derive(Default)]
+    struct Fnv(u64);
+
+    impl std::hash::Hasher for Fnv {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            for byte in bytes {
+                self.0 = (self.0 ^ *byte as u64).wrapping_mul(0x100_0000_01b3);
+            }
+        }
+    }
+
+    #[test]
+    fn works_with_a_custom_hasher() {
+        let map: CacheMap<u32, &str, std::hash::BuildHasherDefault<Fnv>> =
+            CacheMap::with_hasher(Default::default());
+
+        map.insert(1, "one");
+
+        assert_eq!(*map.get_or_insert_with(1, || "uno"), "one");
+        assert_eq!(*map.get_or_insert_with(2, || "two"), "two");
+        assert_eq!(map.remove(&1), Some("one"));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn reserve_grows_capacity_ahead_of_inserts() {
+        let map: CacheMap<u32, u32> = CacheMap::new();
+
+        map.reserve(100);
+
+        assert!(map.capacity() >= 100);
+    }
+
+    #[test]
+    fn auto_reserve_doubles_at_the_load_factor() {
+        let map: CacheMap<u32, u32> = CacheMap::new();
+
+        map.set_auto_reserve(Some(0.5));
+
+        for key in 0..64 {
+            map.insert(key, key);
+
+            assert!(map.capacity() >= map.len() * 2, "len {}", map.len());
+        }
+    }
+
+    #[test]
+    fn validated_lookups_refill_invalid_entries() {
+        let map = CacheMap::new();
+
+        map.insert("path", vec![1, 2, 3]);
+
+        let kept = map.get_or_insert_with_validated("path", |p| p.len() == 3, || vec![9]);
+        assert_eq!(*kept, vec![1, 2, 3]);
+        drop(kept);
+
+        let refilled = map.get_or_insert_with_validated("path", |p| p.is_empty(), || vec![4]);
+        assert_eq!(*refilled, vec![4]);
+        drop(refilled);
+
+        let filled = map.get_or_insert_with_validated("new", |_| true, || vec![5]);
+        assert_eq!(*filled, vec![5]);
+    }
+
+    #[test]
+    fn get_fresh_refills_even_on_a_hit() {
+        let map = CacheMap::new();
+        let fills = Cell::new(0);
+
+        assert_eq!(*map.get_or_insert_with(1, || 10), 10);
+
+        let fresh = map.get_fresh(1, || {
+            fills.set(fills.get() + 1);
+            20
+        });
+        assert_eq!(*fresh, 20);
+        drop(fresh);
+
+        assert_eq!(*map.get_fresh(2, || 30), 30);
+        assert_eq!(fills.get(), 1);
+        assert_eq!(*map.get(&1).unwrap(), 20);
+        assert_eq!(*map.get_or_insert_with(1, || unreachable!()), 20);
+    }
+
+    #[test]
+    fn force_fills_boxed_accessors_lazily_per_key() {
+        let (mut first, mut second) = (None, None);
+        let fills = Cell::new(0);
+        let fills = &fills;
+        let fill = |value: u32| {
+            move || {
+                fills.set(fills.get() + 1);
+                value
+            }
+        };
+
+        let map = CacheMap::new();
+        map.insert(1, (&mut first).access(|_| false, fill(10)).boxed());
+        map.insert(2, (&mut second).access(|_| false, fill(20)).boxed());
+
+        assert_eq!(fills.get(), 0);
+
+        assert_eq!(map.force(&1).map(|v| **v), Some(10));
+        assert_eq!(fills.get(), 1);
+        assert!(!map.get(&2).unwrap().is_forced());
+
+        assert_eq!(map.force(&1).map(|v| **v), Some(10));
+        assert_eq!(map.force(&2).map(|v| **v), Some(20));
+        assert_eq!(fills.get(), 2);
+        assert!(map.force(&3).is_none());
+    }
+
+    #[test]
+    fn extract_if_returns_removed_entries() {
+        let map = CacheMap::new();
+
+        for i in 0..6 {
+            map.insert(i, i * 10);
+        }
+
+        let mut removed = map.extract_if(|k, _| k % 2 == 0);
+        removed.sort();
+
+        assert_eq!(removed, vec![(0, 0), (2, 20), (4, 40)]);
+        assert_eq!(map.len(), 3);
+        assert!(map.contains_key(&1));
+        assert!(!map.contains_key(&2));
+
+        assert!(map.extract_if(|_, v| *v > 100).is_empty());
+        assert_eq!(map.len(), 3);
+    }
+
+    #[test]
+    fn merge_without_collisions_keeps_both_sides() {
+        let map = CacheMap::new();
+        let other = CacheMap::new();
+
+        map.insert("a", 1);
+        other.insert("b", 2);
+
+        map.merge(other, |_, _| unreachable!());
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(*map.get("a").unwrap(), 1);
+        assert_eq!(*map.get("b").unwrap(), 2);
+    }
+
+    #[test]
+    fn merge_resolves_collisions_in_order() {
+        let map = CacheMap::new();
+        let other = CacheMap::new();
+
+        map.insert("a", vec![1]);
+        other.insert("a", vec![2, 3]);
+
+        map.merge(other, |ours, theirs| [ours.as_slice(), &theirs].concat());
+
+        assert_eq!(*map.get("a").unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn decay_mutates_values_and_drops_below_threshold() {
+        let map = CacheMap::new();
+
+        map.insert("keep", 10.0f64);
+        map.insert("fade", 1.2);
+
+        assert!(map.decay(|v| *v *= 0.9, |v| *v < 1.0).is_empty());
+        assert_eq!(*map.get("keep").unwrap(), 9.0);
+
+        let dropped = map.decay(|v| *v *= 0.9, |v| *v < 1.0);
+
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0].0, "fade");
+        assert!(dropped[0].1 < 1.0);
+        assert_eq!(map.len(), 1);
+        assert!((*map.get("keep").unwrap() - 8.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn watermarks_fire_on_crossing_during_insert_and_remove() {
+        let map = CacheMap::new();
+        let crossings = Rc::new(RefCell::new(Vec::new()));
+        let log = crossings.clone();
+
+        map.set_watermarks(1, 3, move |mark| log.borrow_mut().push(mark));
+
+        for i in 0..4 {
+            map.insert(i, i);
+        }
+        assert_eq!(crossings.take(), vec![Watermark::High]);
+
+        map.insert(0, 10);
+        map.remove(&3);
+        map.remove(&2);
+        assert!(crossings.take().is_empty());
+
+        map.remove(&1);
+        map.remove(&0);
+        assert_eq!(crossings.take(), vec![Watermark::Low]);
+
+        map.clear_watermarks();
+        for i in 0..4 {
+            map.insert(i, i);
+        }
+        assert!(crossings.take().is_empty());
+    }
+
+    #[test]
+    fn decay_keeps_the_weight_in_sync() {
+        let map = CacheMap::new();
+
+        map.set_weigher(|v: &u64| *v);
+        map.insert(1, 8);
+        map.insert(2, 2);
+
+        map.decay(|v| *v /= 2, |v| *v == 0);
+        assert_eq!(map.total_weight(), 5);
+
+        map.decay(|v| *v /= 2, |v| *v == 0);
+        assert_eq!(map.total_weight(), 2);
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn get_or_insert_many_fills_each_unique_key_once() {
+        let map = CacheMap::new();
+        let calls = Cell::new(0);
+
+        map.insert(9, 90);
+
+        let values: Vec<u32> = map
+            .get_or_insert_many(vec![1, 2, 1, 9, 2, 3, 1], |k| {
+                calls.set(calls.get() + 1);
+                k * 10
+            })
+            .iter()
+            .map(|v| **v)
+            .collect();
+
+        assert_eq!(values, vec![10, 20, 10, 90, 20, 30, 10]);
+        assert_eq!(calls.get(), 3);
+        assert_eq!(map.len(), 4);
+    }
+
+    #[test]
+    fn read_through_covers_local_shared_and_miss_paths() {
+        let local = CacheMap::new();
+        let shared = CacheMap::new();
+        let calls = Cell::new(0);
+        let fill = |value: u32| {
+            let calls = &calls;
+
+            move || {
+                calls.set(calls.get() + 1);
+                value
+            }
+        };
+
+        local.insert("local", 1);
+        shared.insert("local", 10);
+        shared.insert("shared", 2);
+
+        assert_eq!(
+            *local.get_or_insert_read_through("local", &shared, fill(0)),
+            1
+        );
+
+        assert_eq!(
+            *local.get_or_insert_read_through("shared", &shared, fill(0)),
+            2
+        );
+        assert!(local.contains_key("shared"));
+
+        assert_eq!(
+            *local.get_or_insert_read_through("missing", &shared, fill(3)),
+            3
+        );
+        assert!(!shared.contains_key("missing"));
+
+        assert_eq!(calls.get(), 1);
+        assert_eq!(local.len(), 3);
+    }
+
+    #[test]
+    fn sweep_expired_removes_entries_past_their_validity() {
+        let map = CacheMap::new();
+
+        map.insert("a", ValidUntil::new(1, 5));
+        map.insert("b", ValidUntil::new(2, 10));
+        map.insert("c", ValidUntil::new(3, 3));
+
+        let mut swept = map.sweep_expired(5);
+        swept.sort();
+
+        assert_eq!(swept, vec![("a", 1), ("c", 3)]);
+        assert_eq!(map.len(), 1);
+        assert!(map.contains_key("b"));
+    }
+
+    #[test]
+    fn extract_if_reports_evictions() {
+        let map = CacheMap::new();
+        let events = record_events(&map);
+
+        map.insert(1, 5);
+        map.insert(2, 6);
+        events.take();
+
+        map.extract_if(|_, v| *v == 6);
+
+        assert_eq!(events.take(), vec!["evicted 2 6"]);
+    }
+}
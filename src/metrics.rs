@@ -0,0 +1,93 @@
+use super::*;
+use std::time::{Duration, Instant};
+
+pub trait MetricsRecorder {
+    fn record_hit(&self);
+
+    fn record_miss(&self);
+
+    fn record_fill_duration(&self, duration: Duration);
+}
+
+impl<'c, T, C, X, F, R> CacheAccesor<'c, T, C, X, F, R>
+where
+    F: FnOnce() -> T,
+    X: FnOnce(&T) -> bool,
+    C: FastCacheGet<'c, T, R> + FastCacheExpiration<T>,
+{
+    pub fn get_metered<M>(&mut self, recorder: &M) -> &R
+    where
+        M: MetricsRecorder + ?Sized,
+    {
+        let (data, outcome) = self.get_wrapping_fill(|_, fill| {
+            let start = Instant::now();
+            let value = cold_fill(fill);
+
+            recorder.record_fill_duration(start.elapsed());
+
+            value
+        });
+
+        match outcome {
+            GetOutcome::Hit => recorder.record_hit(),
+            GetOutcome::ExpiredRefilled | GetOutcome::InitialFill => recorder.record_miss(),
+        }
+
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Recording {
+        hits: Cell<u32>,
+        misses: Cell<u32>,
+        fills: RefCell<Vec<Duration>>,
+    }
+
+    impl MetricsRecorder for Recording {
+        fn record_hit(&self) {
+            self.hits.set(self.hits.get() + 1);
+        }
+
+        fn record_miss(&self) {
+            self.misses.set(self.misses.get() + 1);
+        }
+
+        fn record_fill_duration(&self, duration: Duration) {
+            self.fills.borrow_mut().push(duration);
+        }
+    }
+
+    #[test]
+    fn reports_hits_misses_and_fill_durations() {
+        let recorder = Recording::default();
+        let mut cache = None;
+
+        assert_eq!(
+            **(&mut cache).access(|_| false, || 1).get_metered(&recorder),
+            1
+        );
+        assert_eq!(
+            **(&mut cache).access(|_| false, || 2).get_metered(&recorder),
+            1
+        );
+        assert_eq!(
+            **(&mut cache).access(|_| false, || 2).get_metered(&recorder),
+            1
+        );
+
+        let dynamic: &dyn MetricsRecorder = &recorder;
+        assert_eq!(
+            **(&mut cache).access(|_| true, || 3).get_metered(dynamic),
+            3
+        );
+
+        assert_eq!(recorder.hits.get(), 2);
+        assert_eq!(recorder.misses.get(), 2);
+        assert_eq!(recorder.fills.borrow().len(), 2);
+    }
+}
@@ -0,0 +1,173 @@
+use std::borrow::Borrow;
+use std::cell::*;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+pub struct NestedCacheMap<A, B, V> {
+    entries: RefCell<HashMap<A, HashMap<B, V>>>,
+}
+
+impl<A, B, V> Default for NestedCacheMap<A, B, V> {
+    fn default() -> Self {
+        NestedCacheMap {
+            entries: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+impl<A, B, V> NestedCacheMap<A, B, V>
+where
+    A: Hash + Eq,
+    B: Hash + Eq,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries
+            .borrow()
+            .values()
+            .map(|inner| inner.len())
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.borrow().values().all(|inner| inner.is_empty())
+    }
+
+    pub fn get<QA, QB>(&self, a: &QA, b: &QB) -> Option<Ref<'_, V>>
+    where
+        A: Borrow<QA>,
+        B: Borrow<QB>,
+        QA: Hash + Eq + ?Sized,
+        QB: Hash + Eq + ?Sized,
+    {
+        Ref::filter_map(self.entries.borrow(), |e| {
+            e.get(a).and_then(|inner| inner.get(b))
+        })
+        .ok()
+    }
+
+    pub fn insert(&self, a: A, b: B, value: V) -> Option<V> {
+        self.entries
+            .borrow_mut()
+            .entry(a)
+            .or_default()
+            .insert(b, value)
+    }
+
+    pub fn remove<QA, QB>(&self, a: &QA, b: &QB) -> Option<V>
+    where
+        A: Borrow<QA>,
+        B: Borrow<QB>,
+        QA: Hash + Eq + ?Sized,
+        QB: Hash + Eq + ?Sized,
+    {
+        let mut entries = self.entries.borrow_mut();
+
+        let inner = entries.get_mut(a)?;
+        let removed = inner.remove(b);
+
+        if inner.is_empty() {
+            entries.remove(a);
+        }
+
+        removed
+    }
+
+    pub fn remove_all<QA>(&self, a: &QA) -> Option<HashMap<B, V>>
+    where
+        A: Borrow<QA>,
+        QA: Hash + Eq + ?Sized,
+    {
+        self.entries.borrow_mut().remove(a)
+    }
+
+    pub fn clear(&self) {
+        self.entries.borrow_mut().clear();
+    }
+
+    pub fn get_or_insert_with<F>(&self, a: A, b: B, filler: F) -> Ref<'_, V>
+    where
+        A: Clone,
+        B: Clone,
+        F: FnOnce() -> V,
+    {
+        let missing = self
+            .entries
+            .borrow()
+            .get(&a)
+            .map(|inner| !inner.contains_key(&b))
+            .unwrap_or(true);
+
+        if missing {
+            let value = filler();
+
+            self.entries
+                .borrow_mut()
+                .entry(a.clone())
+                .or_default()
+                .insert(b.clone(), value);
+        }
+
+        Ref::map(self.entries.borrow(), |e| &e[&a][&b])
+    }
+
+    pub fn for_each_in<QA, G>(&self, a: &QA, mut f: G)
+    where
+        A: Borrow<QA>,
+        QA: Hash + Eq + ?Sized,
+        G: FnMut(&B, &V),
+    {
+        if let Some(inner) = self.entries.borrow().get(a) {
+            for (b, v) in inner.iter() {
+                f(b, v);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inserts_under_two_level_keys() {
+        let map = NestedCacheMap::new();
+
+        map.insert("W1N1", 1, "spawn");
+        map.insert("W1N1", 2, "tower");
+        map.insert("W2N2", 1, "link");
+
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.get("W1N1", &2).map(|v| *v), Some("tower"));
+        assert_eq!(*map.get_or_insert_with("W2N2", 1, || "unused"), "link");
+        assert_eq!(*map.get_or_insert_with("W2N2", 3, || "lab"), "lab");
+
+        let mut inner = Vec::new();
+        map.for_each_in("W1N1", |b, v| inner.push((*b, *v)));
+        inner.sort();
+
+        assert_eq!(inner, vec![(1, "spawn"), (2, "tower")]);
+    }
+
+    #[test]
+    fn remove_all_drops_one_outer_key() {
+        let map = NestedCacheMap::new();
+
+        map.insert(1, 'a', 10);
+        map.insert(1, 'b', 11);
+        map.insert(2, 'a', 20);
+
+        let removed = map.remove_all(&1).unwrap();
+
+        assert_eq!(removed.len(), 2);
+        assert!(map.get(&1, &'a').is_none());
+        assert_eq!(map.get(&2, &'a').map(|v| *v), Some(20));
+        assert!(map.remove_all(&1).is_none());
+
+        assert_eq!(map.remove(&2, &'a'), Some(20));
+        assert!(map.is_empty());
+    }
+}
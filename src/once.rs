@@ -0,0 +1,95 @@
+use std::cell::OnceCell;
+
+pub struct RefreshableOnce<T> {
+    cell: OnceCell<T>,
+    epoch: u64,
+}
+
+impl<T> Default for RefreshableOnce<T> {
+    fn default() -> Self {
+        RefreshableOnce {
+            cell: OnceCell::new(),
+            epoch: 0,
+        }
+    }
+}
+
+impl<T> RefreshableOnce<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    pub fn get(&self) -> Option<&T> {
+        self.cell.get()
+    }
+
+    pub fn get_or_init<F>(&self, filler: F) -> &T
+    where
+        F: FnOnce() -> T,
+    {
+        self.cell.get_or_init(filler)
+    }
+
+    pub fn reset(&mut self) -> Option<T> {
+        self.epoch += 1;
+
+        std::mem::take(&mut self.cell).into_inner()
+    }
+
+    pub fn reset_if<X>(&mut self, expiration: X) -> Option<T>
+    where
+        X: FnOnce(&T) -> bool,
+    {
+        if self.cell.get().map(expiration).unwrap_or(false) {
+            self.reset()
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn fills_once_per_epoch() {
+        let mut once = RefreshableOnce::new();
+        let fills = Cell::new(0);
+        let fill = |value: &'static str| {
+            fills.set(fills.get() + 1);
+            value
+        };
+
+        assert!(once.get().is_none());
+        assert_eq!(*once.get_or_init(|| fill("first")), "first");
+        assert_eq!(*once.get_or_init(|| fill("ignored")), "first");
+        assert_eq!(once.epoch(), 0);
+
+        assert_eq!(once.reset(), Some("first"));
+        assert_eq!(once.epoch(), 1);
+        assert!(once.get().is_none());
+
+        assert_eq!(*once.get_or_init(|| fill("second")), "second");
+        assert_eq!(*once.get_or_init(|| fill("ignored")), "second");
+        assert_eq!(fills.get(), 2);
+    }
+
+    #[test]
+    fn reset_if_only_resets_expired_values() {
+        let mut once = RefreshableOnce::new();
+
+        assert_eq!(once.reset_if(|_: &u32| true), None);
+        assert_eq!(once.epoch(), 0);
+
+        once.get_or_init(|| 3);
+        assert_eq!(once.reset_if(|v| *v > 5), None);
+        assert_eq!(once.reset_if(|v| *v == 3), Some(3));
+        assert_eq!(once.epoch(), 1);
+    }
+}
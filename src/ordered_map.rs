@@ -0,0 +1,149 @@
+use std::borrow::Borrow;
+use std::cell::*;
+use std::collections::BTreeMap;
+use std::ops::*;
+
+pub struct OrderedCacheMap<K, V> {
+    entries: RefCell<BTreeMap<K, V>>,
+}
+
+impl<K, V> Default for OrderedCacheMap<K, V> {
+    fn default() -> Self {
+        OrderedCacheMap {
+            entries: RefCell::new(BTreeMap::new()),
+        }
+    }
+}
+
+impl<K, V> OrderedCacheMap<K, V>
+where
+    K: Ord,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.borrow().is_empty()
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.entries.borrow().contains_key(key)
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<Ref<'_, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        Ref::filter_map(self.entries.borrow(), |e| e.get(key)).ok()
+    }
+
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        self.entries.borrow_mut().insert(key, value)
+    }
+
+    pub fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.entries.borrow_mut().remove(key)
+    }
+
+    pub fn clear(&self) {
+        self.entries.borrow_mut().clear();
+    }
+
+    pub fn get_or_insert_with<F>(&self, key: K, filler: F) -> Ref<'_, V>
+    where
+        K: Clone,
+        F: FnOnce() -> V,
+    {
+        if !self.entries.borrow().contains_key(&key) {
+            let value = filler();
+
+            self.entries.borrow_mut().insert(key.clone(), value);
+        }
+
+        Ref::map(self.entries.borrow(), |e| &e[&key])
+    }
+
+    pub fn remove_range<Q, B>(&self, range: B)
+    where
+        K: Borrow<Q> + Clone,
+        Q: Ord + ?Sized,
+        B: RangeBounds<Q>,
+    {
+        let mut entries = self.entries.borrow_mut();
+
+        let keys: Vec<K> = entries.range(range).map(|(k, _)| k.clone()).collect();
+
+        for key in keys {
+            entries.remove::<K>(&key);
+        }
+    }
+}
+
+impl<V> OrderedCacheMap<String, V> {
+    pub fn remove_prefix(&self, prefix: &str) {
+        let mut entries = self.entries.borrow_mut();
+
+        let keys: Vec<String> = entries
+            .range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+            .take_while(|(k, _)| k.starts_with(prefix))
+            .map(|(k, _)| k.clone())
+            .collect();
+
+        for key in keys {
+            entries.remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_prefix_keeps_other_keys() {
+        let map = OrderedCacheMap::new();
+
+        for key in ["W1N1.a", "W1N1.b", "W1N10.a", "W1N2.a", "W1N"] {
+            map.insert(key.to_string(), key.len());
+        }
+
+        map.remove_prefix("W1N1.");
+
+        assert!(!map.contains_key("W1N1.a"));
+        assert!(!map.contains_key("W1N1.b"));
+        assert!(map.contains_key("W1N10.a"));
+        assert!(map.contains_key("W1N2.a"));
+        assert!(map.contains_key("W1N"));
+        assert_eq!(map.len(), 3);
+    }
+
+    #[test]
+    fn remove_range_keeps_keys_outside_the_range() {
+        let map = OrderedCacheMap::new();
+
+        for key in 0..10u32 {
+            map.insert(key, key * 2);
+        }
+
+        map.remove_range(3..7);
+
+        let remaining: Vec<u32> = (0..10).filter(|k| map.contains_key(k)).collect();
+
+        assert_eq!(remaining, vec![0, 1, 2, 7, 8, 9]);
+        assert_eq!(map.get(&8).map(|v| *v), Some(16));
+    }
+}
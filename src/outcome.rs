@@ -0,0 +1,88 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GetOutcome {
+    Hit,
+    ExpiredRefilled,
+    InitialFill,
+}
+
+impl<'c, T, C, X, F, R> CacheAccesor<'c, T, C, X, F, R>
+where
+    F: FnOnce() -> T,
+    X: FnOnce(&T) -> bool,
+    C: FastCacheGet<'c, T, R> + FastCacheExpiration<T>,
+{
+    pub fn get_detailed(&mut self) -> (&R, GetOutcome) {
+        self.get_wrapping_fill(|_, fill| cold_fill(fill))
+    }
+
+    pub(crate) fn get_wrapping_fill<W>(&mut self, wrap_fill: W) -> (&R, GetOutcome)
+    where
+        W: FnOnce(bool, F) -> T,
+    {
+        let expired = Cell::new(false);
+        let filled = Cell::new(false);
+
+        take_mut::take(&mut self.state, |v| match v {
+            CacheState::Unknown(state, _) => {
+                let expiration = state.expiration;
+                let fill = state.fill;
+
+                let data = state
+                    .cache
+                    .expire_with(|v| {
+                        let is_expired = expiration(v);
+
+                        expired.set(is_expired);
+
+                        is_expired
+                    })
+                    .get_or_insert_with(|| {
+                        filled.set(true);
+
+                        wrap_fill(expired.get(), fill)
+                    });
+
+                CacheState::Known(CacheStateKnown { data })
+            }
+            v => v,
+        });
+
+        let outcome = match (filled.get(), expired.get()) {
+            (true, true) => GetOutcome::ExpiredRefilled,
+            (true, false) => GetOutcome::InitialFill,
+            (false, _) => GetOutcome::Hit,
+        };
+
+        match &self.state {
+            CacheState::Unknown(_, _) => unsafe { std::hint::unreachable_unchecked() },
+            CacheState::Known(s) => (&s.data, outcome),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_detailed_distinguishes_each_outcome() {
+        let mut cache = None;
+
+        let mut accessor = (&mut cache).access(|_| true, || 1);
+        let (value, outcome) = accessor.get_detailed();
+        assert_eq!((**value, outcome), (1, GetOutcome::InitialFill));
+
+        let (value, outcome) = accessor.get_detailed();
+        assert_eq!((**value, outcome), (1, GetOutcome::Hit));
+
+        let mut accessor = (&mut cache).access(|_| false, || 2);
+        let (value, outcome) = accessor.get_detailed();
+        assert_eq!((**value, outcome), (1, GetOutcome::Hit));
+
+        let mut accessor = (&mut cache).access(|v| *v == 1, || 3);
+        let (value, outcome) = accessor.get_detailed();
+        assert_eq!((**value, outcome), (3, GetOutcome::ExpiredRefilled));
+    }
+}
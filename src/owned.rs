@@ -0,0 +1,225 @@
+use super::*;
+
+pub struct OwnedCache<T> {
+    data: Option<T>,
+}
+
+impl<T> Default for OwnedCache<T> {
+    fn default() -> Self {
+        OwnedCache { data: None }
+    }
+}
+
+impl<T> From<Option<T>> for OwnedCache<T> {
+    fn from(data: Option<T>) -> Self {
+        OwnedCache { data }
+    }
+}
+
+impl<T> OwnedCache<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_some(&self) -> bool {
+        self.data.is_some()
+    }
+
+    pub fn is_none(&self) -> bool {
+        self.data.is_none()
+    }
+
+    pub fn as_option(&self) -> Option<&T> {
+        self.data.as_ref()
+    }
+
+    pub fn clear(&mut self) -> Option<T> {
+        self.data.take()
+    }
+
+    pub fn into_option(self) -> Option<T> {
+        self.data
+    }
+}
+
+impl<'c, T, X, F> CacheAccesor<'c, T, &'c mut OwnedCache<T>, X, F, &'c T>
+where
+    X: FnOnce(&T) -> bool,
+    F: FnOnce() -> T,
+{
+    // Resolves the accessor and returns the still-populated backend with the
+    // value. An accessor already resolved through `get` has given up its
+    // backend borrow to the value, so only the value is left to return.
+    pub fn into_parts(self) -> (Option<&'c OwnedCache<T>>, &'c T) {
+        match self.state {
+            CacheState::Unknown(state, _) => {
+                let fill = state.fill;
+                let cache = state.cache.expire_with(state.expiration);
+
+                cache.data.get_or_insert_with(move || cold_fill(fill));
+
+                let cache: &'c OwnedCache<T> = cache;
+
+                match &cache.data {
+                    Some(value) => (Some(cache), value),
+                    None => unreachable!(),
+                }
+            }
+            CacheState::Known(s) => (None, s.data),
+        }
+    }
+}
+
+impl<T> FastCacheExpiration<T> for &mut OwnedCache<T> {
+    fn expire_with<X>(self, expiration: X) -> Self
+    where
+        X: FnOnce(&T) -> bool,
+    {
+        (&mut self.data).expire_with(expiration);
+
+        self
+    }
+}
+
+impl<'a, T> FastCacheGet<'a, T, &'a T> for &'a mut OwnedCache<T> {
+    fn get_or_insert_with<F: FnOnce() -> T>(self, f: F) -> &'a T {
+        self.data.get_or_insert_with(f)
+    }
+}
+
+impl<'a, T> FastCacheMaybeGet<'a, T, &'a T> for &'a mut OwnedCache<T> {
+    fn maybe_get_or_insert_with<F: FnOnce() -> Option<T>>(self, f: F) -> Option<&'a T> {
+        (&mut self.data).maybe_get_or_insert_with(f)
+    }
+}
+
+impl<'a, T> FastCacheMaybeRetain<'a, T, &'a T> for &'a mut OwnedCache<T> {
+    fn maybe_get_or_retain<F: FnOnce() -> Option<T>>(self, f: F) -> Result<&'a T, Self> {
+        if self.data.is_none() {
+            self.data = (f)();
+        }
+
+        match self.data {
+            Some(ref value) => Ok(value),
+            None => Err(self),
+        }
+    }
+}
+
+impl<T> FastCacheBackend<T> for &mut OwnedCache<T> {
+    fn map_backend<U, M>(self, map: M) -> U
+    where
+        M: FnOnce(&mut Option<T>) -> U,
+    {
+        map(&mut self.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deconstructs_populated_and_empty_caches() {
+        let populated = OwnedCache::from(Some(3));
+
+        assert!(populated.is_some());
+        assert_eq!(populated.into_option(), Some(3));
+
+        let empty: OwnedCache<u32> = OwnedCache::new();
+
+        assert!(empty.is_none());
+        assert_eq!(empty.into_option(), None);
+    }
+
+    struct Room {
+        tick: u32,
+        energy: OwnedCache<(u32, u32)>,
+    }
+
+    impl Room {
+        fn energy(&mut self, measure: u32) -> u32 {
+            let tick = self.tick;
+
+            (&mut self.energy)
+                .access(|v| v.0 != tick, || (tick, measure))
+                .take()
+                .1
+        }
+    }
+
+    #[test]
+    fn owned_cache_fields_mirror_option_semantics() {
+        let mut room = Room {
+            tick: 1,
+            energy: OwnedCache::new(),
+        };
+
+        assert_eq!(room.energy(100), 100);
+        assert_eq!(room.energy(200), 100);
+
+        room.tick = 2;
+
+        assert_eq!(room.energy(300), 300);
+    }
+
+    #[test]
+    fn owned_cache_supports_maybe_accessors() {
+        let mut cache: OwnedCache<u32> = OwnedCache::new();
+
+        assert!((&mut cache)
+            .maybe_access(|_| false, || None)
+            .take()
+            .is_none());
+        assert!(cache.is_none());
+
+        let value = (&mut cache)
+            .maybe_access(|_| false, || Some(2))
+            .take()
+            .copied();
+
+        assert_eq!(value, Some(2));
+        assert_eq!(cache.clear(), Some(2));
+    }
+
+    #[test]
+    fn into_parts_returns_a_populated_reusable_backend() {
+        let mut cache = OwnedCache::new();
+        let fills = Cell::new(0);
+        let fill = |value: u32| {
+            let fills = &fills;
+
+            move || {
+                fills.set(fills.get() + 1);
+                value
+            }
+        };
+
+        let (backend, value) = (&mut cache).access(|_| false, fill(1)).into_parts();
+        assert_eq!(*value, 1);
+        assert_eq!(backend.and_then(OwnedCache::as_option), Some(&1));
+
+        let (backend, value) = (&mut cache).access(|_| false, fill(2)).into_parts();
+        assert_eq!(*value, 1);
+        assert!(backend.is_some_and(OwnedCache::is_some));
+
+        let (_, value) = (&mut cache).access(|v| *v == 1, fill(3)).into_parts();
+        assert_eq!(*value, 3);
+
+        assert_eq!(*(&mut cache).access(|_| false, fill(4)).get(), &3);
+        assert_eq!(fills.get(), 2);
+    }
+
+    #[test]
+    fn into_parts_after_get_returns_only_the_value() {
+        let mut cache = OwnedCache::new();
+
+        let mut accessor = (&mut cache).access(|_| false, || 5);
+        assert_eq!(**accessor.get(), 5);
+
+        let (backend, value) = accessor.into_parts();
+        assert!(backend.is_none());
+        assert_eq!(*value, 5);
+        assert_eq!(cache.into_option(), Some(5));
+    }
+}
@@ -0,0 +1,41 @@
+use super::*;
+use rayon::prelude::*;
+
+pub fn force_all<R>(accessors: Vec<SendBoxedAccessor<'_, R>>) -> Vec<R>
+where
+    R: Send,
+{
+    accessors
+        .into_par_iter()
+        .map(SendBoxedAccessor::take)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn force_all_returns_each_fill_in_order() {
+        let caches: Vec<Mutex<Option<Arc<u64>>>> = (0..32).map(|_| Mutex::new(None)).collect();
+
+        let accessors = caches
+            .iter()
+            .enumerate()
+            .map(|(i, cache)| {
+                cache
+                    .access(|_| false, move || Arc::new((0..=i as u64).sum()))
+                    .send_boxed()
+            })
+            .collect();
+
+        let values: Vec<u64> = force_all(accessors).into_iter().map(|v| *v).collect();
+
+        assert_eq!(
+            values,
+            (0..32u64).map(|i| i * (i + 1) / 2).collect::<Vec<_>>()
+        );
+        assert!(caches.iter().all(|c| c.lock().unwrap().is_some()));
+    }
+}
@@ -0,0 +1,87 @@
+use super::*;
+use std::pin::Pin;
+
+pub struct PinnedCache<T> {
+    data: Option<Pin<Box<T>>>,
+}
+
+impl<T> Default for PinnedCache<T> {
+    fn default() -> Self {
+        PinnedCache { data: None }
+    }
+}
+
+impl<T> PinnedCache<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self) -> Option<Pin<&T>> {
+        self.data.as_ref().map(|v| v.as_ref())
+    }
+
+    pub fn clear(&mut self) {
+        self.data = None;
+    }
+
+    pub fn get_or_insert_with<X, F>(&mut self, expiration: X, filler: F) -> Pin<&T>
+    where
+        X: FnOnce(&T) -> bool,
+        F: FnOnce() -> T,
+    {
+        (&mut self.data)
+            .access(
+                move |v: &Pin<Box<T>>| expiration(v),
+                move || Box::pin(filler()),
+            )
+            .take()
+            .as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::marker::PhantomPinned;
+
+    struct Intrusive {
+        value: u32,
+        _pin: PhantomPinned,
+    }
+
+    fn address(value: Pin<&Intrusive>) -> *const Intrusive {
+        &*value
+    }
+
+    #[test]
+    fn address_is_stable_across_reads() {
+        let mut cache = PinnedCache::new();
+
+        let first = address(cache.get_or_insert_with(
+            |_| false,
+            || Intrusive {
+                value: 1,
+                _pin: PhantomPinned,
+            },
+        ));
+
+        for _ in 0..3 {
+            let again = cache.get_or_insert_with(|_| false, || unreachable!());
+            assert_eq!(again.value, 1);
+            assert_eq!(address(again), first);
+        }
+
+        assert_eq!(cache.get().map(address), Some(first));
+    }
+
+    #[test]
+    fn expiration_and_clear_drop_the_pinned_value() {
+        let mut cache = PinnedCache::new();
+
+        assert_eq!(*cache.get_or_insert_with(|_| false, || 1), 1);
+        assert_eq!(*cache.get_or_insert_with(|v| *v == 1, || 2), 2);
+
+        cache.clear();
+        assert!(cache.get().is_none());
+    }
+}
@@ -0,0 +1,138 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValueMeta {
+    pub fill_tick: u32,
+    pub current_tick: u32,
+}
+
+impl ValueMeta {
+    pub fn age(&self) -> u32 {
+        self.current_tick.wrapping_sub(self.fill_tick)
+    }
+}
+
+pub trait ExpirationPolicy {
+    fn should_expire(&self, value_meta: &ValueMeta) -> bool;
+}
+
+pub struct TtlPolicy(pub u32);
+
+impl ExpirationPolicy for TtlPolicy {
+    fn should_expire(&self, value_meta: &ValueMeta) -> bool {
+        value_meta.age() >= self.0
+    }
+}
+
+pub struct NeverExpire;
+
+impl ExpirationPolicy for NeverExpire {
+    fn should_expire(&self, _value_meta: &ValueMeta) -> bool {
+        false
+    }
+}
+
+pub trait FastCachePolicyAccessor<'a, T, R>: FastCacheAccessor<'a, TickStamped<T>, R> {
+    #[allow(clippy::type_complexity)]
+    fn access_with_policy<'p, F>(
+        self,
+        policy: &'p dyn ExpirationPolicy,
+        current_tick: u32,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        TickStamped<T>,
+        Self,
+        impl FnOnce(&TickStamped<T>) -> bool + 'p,
+        impl FnOnce() -> TickStamped<T>,
+        R,
+    >
+    where
+        F: FnOnce() -> T;
+}
+
+impl<'a, C, T, R> FastCachePolicyAccessor<'a, T, R> for C
+where
+    C: FastCacheAccessor<'a, TickStamped<T>, R>,
+{
+    #[allow(clippy::type_complexity)]
+    fn access_with_policy<'p, F>(
+        self,
+        policy: &'p dyn ExpirationPolicy,
+        current_tick: u32,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        TickStamped<T>,
+        Self,
+        impl FnOnce(&TickStamped<T>) -> bool + 'p,
+        impl FnOnce() -> TickStamped<T>,
+        R,
+    >
+    where
+        F: FnOnce() -> T,
+    {
+        self.access(
+            move |v: &TickStamped<T>| {
+                policy.should_expire(&ValueMeta {
+                    fill_tick: v.tick,
+                    current_tick,
+                })
+            },
+            move || TickStamped::new(filler(), current_tick),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct EveryOtherTick;
+
+    impl ExpirationPolicy for EveryOtherTick {
+        fn should_expire(&self, value_meta: &ValueMeta) -> bool {
+            value_meta.current_tick.is_multiple_of(2) && value_meta.age() > 0
+        }
+    }
+
+    fn fetch(
+        cache: &mut Option<TickStamped<u32>>,
+        policy: &dyn ExpirationPolicy,
+        tick: u32,
+    ) -> u32 {
+        cache
+            .access_with_policy(policy, tick, move || tick)
+            .take()
+            .value
+    }
+
+    #[test]
+    fn policies_decide_expiry_of_the_same_value() {
+        let stored = ValueMeta {
+            fill_tick: 10,
+            current_tick: 14,
+        };
+
+        assert!(TtlPolicy(3).should_expire(&stored));
+        assert!(!TtlPolicy(5).should_expire(&stored));
+        assert!(!NeverExpire.should_expire(&stored));
+        assert!(EveryOtherTick.should_expire(&stored));
+    }
+
+    #[test]
+    fn accessors_consult_the_configured_policy() {
+        let policies: [&dyn ExpirationPolicy; 2] = [&TtlPolicy(3), &NeverExpire];
+        let refilled: Vec<u32> = policies
+            .iter()
+            .map(|policy| {
+                let mut cache = Some(TickStamped::new(0, 0));
+
+                fetch(&mut cache, *policy, 2);
+                fetch(&mut cache, *policy, 5)
+            })
+            .collect();
+
+        assert_eq!(refilled, vec![5, 0]);
+    }
+}
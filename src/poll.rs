@@ -0,0 +1,427 @@
+use super::*;
+
+pub trait FastCachePollAccessor<'a, T, R>:
+    FastCacheExpiration<T> + FastCacheMaybeGet<'a, T, R> + Copy
+{
+    fn poll_access<X, F>(self, expiration: X, filler: F) -> PollCacheAccesor<'a, T, Self, X, F, R>
+    where
+        F: FnMut() -> Option<T>,
+        X: FnOnce(&T) -> bool;
+}
+
+impl<'a, C, T, R> FastCachePollAccessor<'a, T, R> for C
+where
+    C: FastCacheExpiration<T> + FastCacheMaybeGet<'a, T, R> + Copy,
+{
+    fn poll_access<X, F>(self, expiration: X, filler: F) -> PollCacheAccesor<'a, T, Self, X, F, R>
+    where
+        F: FnMut() -> Option<T>,
+        X: FnOnce(&T) -> bool,
+    {
+        PollCacheAccesor {
+            cache: self,
+            expiration: Some(expiration),
+            fill: filler,
+            phantom: PhantomData,
+        }
+    }
+}
+
+pub struct PollCacheAccesor<'c, T, C, X, F, R>
+where
+    F: FnMut() -> Option<T>,
+    X: FnOnce(&T) -> bool,
+    C: FastCacheMaybeGet<'c, T, R> + FastCacheExpiration<T> + Copy,
+{
+    cache: C,
+    expiration: Option<X>,
+    fill: F,
+    phantom: PhantomData<(&'c C, T, R)>,
+}
+
+impl<'c, T, C, X, F, R> PollCacheAccesor<'c, T, C, X, F, R>
+where
+    F: FnMut() -> Option<T>,
+    X: FnOnce(&T) -> bool,
+    C: FastCacheMaybeGet<'c, T, R> + FastCacheExpiration<T> + Copy,
+{
+    pub fn poll_get(&mut self) -> Option<R> {
+        if let Some(expiration) = self.expiration.take() {
+            self.cache.expire_with(expiration);
+        }
+
+        let fill = &mut self.fill;
+
+        self.cache.maybe_get_or_insert_with(move || cold_fill(fill))
+    }
+}
+
+pub trait FastCacheTickFillAccessor<'a, T, R>:
+    FastCacheExpiration<T> + FastCacheMaybeGet<'a, T, R> + Copy
+{
+    fn tick_fill_access<X, F>(
+        self,
+        expiration: X,
+        step: F,
+    ) -> TickFillCacheAccesor<'a, T, Self, X, F, R>
+    where
+        F: FnMut() -> Option<T>,
+        X: FnOnce(&T) -> bool;
+}
+
+impl<'a, C, T, R> FastCacheTickFillAccessor<'a, T, R> for C
+where
+    C: FastCacheExpiration<T> + FastCacheMaybeGet<'a, T, R> + Copy,
+{
+    fn tick_fill_access<X, F>(
+        self,
+        expiration: X,
+        step: F,
+    ) -> TickFillCacheAccesor<'a, T, Self, X, F, R>
+    where
+        F: FnMut() -> Option<T>,
+        X: FnOnce(&T) -> bool,
+    {
+        TickFillCacheAccesor {
+            cache: self,
+            expiration: Some(expiration),
+            step,
+            phantom: PhantomData,
+        }
+    }
+}
+
+pub struct TickFillCacheAccesor<'c, T, C, X, F, R>
+where
+    F: FnMut() -> Option<T>,
+    X: FnOnce(&T) -> bool,
+    C: FastCacheMaybeGet<'c, T, R> + FastCacheExpiration<T> + Copy,
+{
+    cache: C,
+    expiration: Option<X>,
+    step: F,
+    phantom: PhantomData<(&'c C, T, R)>,
+}
+
+impl<'c, T, C, X, F, R> TickFillCacheAccesor<'c, T, C, X, F, R>
+where
+    F: FnMut() -> Option<T>,
+    X: FnOnce(&T) -> bool,
+    C: FastCacheMaybeGet<'c, T, R> + FastCacheExpiration<T> + Copy,
+{
+    fn apply_expiration(&mut self) {
+        if let Some(expiration) = self.expiration.take() {
+            self.cache.expire_with(expiration);
+        }
+    }
+
+    pub fn advance_tick(&mut self) -> bool {
+        self.apply_expiration();
+
+        let step = &mut self.step;
+
+        self.cache
+            .maybe_get_or_insert_with(move || cold_fill(step))
+            .is_some()
+    }
+
+    pub fn get(&mut self) -> Option<R> {
+        self.apply_expiration();
+
+        self.cache.maybe_get_or_insert_with(|| None)
+    }
+}
+
+#[cfg(feature = "async")]
+mod future {
+    use super::*;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll, Waker};
+
+    #[derive(Default)]
+    pub struct FillNotifier {
+        waker: RefCell<Option<Waker>>,
+    }
+
+    impl FillNotifier {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn notify(&self) {
+            if let Some(waker) = self.waker.borrow_mut().take() {
+                waker.wake();
+            }
+        }
+    }
+
+    pub struct CacheFuture<'c, 'n, T, C, X, F, R>
+    where
+        F: FnMut() -> Option<T>,
+        X: FnOnce(&T) -> bool,
+        C: FastCacheMaybeGet<'c, T, R> + FastCacheExpiration<T> + Copy,
+    {
+        accessor: PollCacheAccesor<'c, T, C, X, F, R>,
+        notifier: &'n FillNotifier,
+    }
+
+    // No field is structurally pinned, so the future can be moved freely.
+    impl<'c, 'n, T, C, X, F, R> Unpin for CacheFuture<'c, 'n, T, C, X, F, R>
+    where
+        F: FnMut() -> Option<T>,
+        X: FnOnce(&T) -> bool,
+        C: FastCacheMaybeGet<'c, T, R> + FastCacheExpiration<T> + Copy,
+    {
+    }
+
+    impl<'c, T, C, X, F, R> PollCacheAccesor<'c, T, C, X, F, R>
+    where
+        F: FnMut() -> Option<T>,
+        X: FnOnce(&T) -> bool,
+        C: FastCacheMaybeGet<'c, T, R> + FastCacheExpiration<T> + Copy,
+    {
+        pub fn into_future(self, notifier: &FillNotifier) -> CacheFuture<'c, '_, T, C, X, F, R> {
+            CacheFuture {
+                accessor: self,
+                notifier,
+            }
+        }
+    }
+
+    impl<'c, 'n, T, C, X, F, R> Future for CacheFuture<'c, 'n, T, C, X, F, R>
+    where
+        F: FnMut() -> Option<T>,
+        X: FnOnce(&T) -> bool,
+        C: FastCacheMaybeGet<'c, T, R> + FastCacheExpiration<T> + Copy,
+    {
+        type Output = R;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<R> {
+            let this = self.get_mut();
+
+            match this.accessor.poll_get() {
+                Some(data) => Poll::Ready(data),
+                None => {
+                    *this.notifier.waker.borrow_mut() = Some(cx.waker().clone());
+
+                    Poll::Pending
+                }
+            }
+        }
+    }
+
+    pub trait FastCacheAsyncAccessor<'a, T, R>:
+        FastCacheExpiration<T> + FastCacheMaybeGet<'a, T, R> + Copy
+    {
+        fn get_or_insert_with_async<X, Fut>(
+            self,
+            expiration: X,
+            fill: Fut,
+        ) -> impl Future<Output = R>
+        where
+            X: FnOnce(&T) -> bool,
+            Fut: Future<Output = T>;
+    }
+
+    impl<'a, C, T, R> FastCacheAsyncAccessor<'a, T, R> for C
+    where
+        C: FastCacheExpiration<T> + FastCacheMaybeGet<'a, T, R> + Copy,
+    {
+        async fn get_or_insert_with_async<X, Fut>(self, expiration: X, fill: Fut) -> R
+        where
+            X: FnOnce(&T) -> bool,
+            Fut: Future<Output = T>,
+        {
+            let cache = self.expire_with(expiration);
+
+            if let Some(data) = cache.maybe_get_or_insert_with(|| None) {
+                return data;
+            }
+
+            // Nothing is written until the fill completes, so dropping
+            // this future part way through leaves the slot empty.
+            let value = fill.await;
+
+            cache
+                .maybe_get_or_insert_with(move || Some(value))
+                .unwrap_or_else(|| unreachable!())
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+pub use future::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poll_get_fills_once_the_value_is_ready() {
+        let cache: RefCell<Option<u32>> = RefCell::new(None);
+        let ready = Cell::new(false);
+
+        let mut accessor =
+            (&cache).poll_access(|_| false, || if ready.get() { Some(3) } else { None });
+
+        assert!(accessor.poll_get().is_none());
+
+        ready.set(true);
+
+        assert_eq!(accessor.poll_get().map(|v| *v), Some(3));
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn awaits_a_value_provided_after_a_delay() {
+        use std::future::Future;
+        use std::pin::pin;
+        use std::rc::Rc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        use std::task::{Context, Poll, Wake, Waker};
+
+        struct CountingWaker(AtomicUsize);
+
+        impl Wake for CountingWaker {
+            fn wake(self: Arc<Self>) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        let waker = Waker::from(counter.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        let cache = RefCell::new(None);
+        let notifier = FillNotifier::new();
+        let delay = Rc::new(Cell::new(2u32));
+        let remaining = delay.clone();
+
+        let mut future = pin!((&cache)
+            .poll_access(
+                |_| false,
+                move || {
+                    remaining.set(remaining.get().saturating_sub(1));
+                    if remaining.get() == 0 {
+                        Some(7)
+                    } else {
+                        None
+                    }
+                }
+            )
+            .into_future(&notifier));
+
+        assert!(future.as_mut().poll(&mut cx).is_pending());
+
+        notifier.notify();
+        assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(value) => assert_eq!(*value, 7),
+            Poll::Pending => panic!("value should be ready"),
+        }
+
+        assert_eq!(*cache.borrow(), Some(7));
+        assert_eq!(delay.get(), 0);
+    }
+
+    #[test]
+    fn tick_fill_completes_after_three_ticks() {
+        let cache: RefCell<Option<Vec<u32>>> = RefCell::new(None);
+        let mut partial = Vec::new();
+
+        let mut accessor = (&cache).tick_fill_access(
+            |_| false,
+            move || {
+                partial.push(partial.len() as u32);
+
+                if partial.len() == 3 {
+                    Some(std::mem::take(&mut partial))
+                } else {
+                    None
+                }
+            },
+        );
+
+        assert!(accessor.get().is_none());
+        assert!(!accessor.advance_tick());
+        assert!(accessor.get().is_none());
+        assert!(!accessor.advance_tick());
+        assert!(accessor.advance_tick());
+
+        assert_eq!(accessor.get().map(|v| v.clone()), Some(vec![0, 1, 2]));
+        assert!(accessor.advance_tick());
+        assert_eq!(cache.borrow().as_ref().map(Vec::len), Some(3));
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn dropping_an_async_fill_leaves_the_slot_empty() {
+        use std::future::Future;
+        use std::pin::{pin, Pin};
+        use std::task::{Context, Poll, Waker};
+
+        struct YieldOnce(bool);
+
+        impl Future for YieldOnce {
+            type Output = ();
+
+            fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()> {
+                if self.0 {
+                    Poll::Ready(())
+                } else {
+                    self.0 = true;
+                    Poll::Pending
+                }
+            }
+        }
+
+        let mut cx = Context::from_waker(Waker::noop());
+        let cache: RefCell<Option<u32>> = RefCell::new(None);
+
+        {
+            let mut future = pin!((&cache).get_or_insert_with_async(|_| false, async {
+                YieldOnce(false).await;
+                5
+            }));
+
+            assert!(future.as_mut().poll(&mut cx).is_pending());
+        }
+
+        assert!(cache.borrow().is_none());
+
+        let mut future = pin!((&cache).get_or_insert_with_async(|_| false, async {
+            YieldOnce(false).await;
+            6
+        }));
+
+        assert!(future.as_mut().poll(&mut cx).is_pending());
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(value) => assert_eq!(*value, 6),
+            Poll::Pending => panic!("fill should have completed"),
+        };
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn cache_futures_can_be_polled_without_pinning() {
+        use std::future::Future;
+        use std::pin::Pin;
+        use std::task::{Context, Poll, Waker};
+
+        let cache = RefCell::new(Some(4u32));
+        let notifier = FillNotifier::new();
+        let mut cx = Context::from_waker(Waker::noop());
+
+        let mut future = (&cache)
+            .poll_access(|_| false, || None)
+            .into_future(&notifier);
+
+        match Pin::new(&mut future).poll(&mut cx) {
+            Poll::Ready(value) => assert_eq!(*value, 4),
+            Poll::Pending => panic!("stored value should be ready"),
+        };
+    }
+}
@@ -0,0 +1,218 @@
+use super::*;
+use std::collections::VecDeque;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CacheDecision {
+    Hit,
+    Miss,
+    Expire,
+}
+
+pub struct DecisionRecorder {
+    capacity: usize,
+    history: RefCell<VecDeque<(CacheDecision, u32)>>,
+}
+
+impl DecisionRecorder {
+    pub fn new(capacity: usize) -> Self {
+        DecisionRecorder {
+            capacity,
+            history: RefCell::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    pub fn record(&self, decision: CacheDecision, tick: u32) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut history = self.history.borrow_mut();
+
+        if history.len() == self.capacity {
+            history.pop_front();
+        }
+
+        history.push_back((decision, tick));
+    }
+
+    pub fn history(&self) -> Vec<(CacheDecision, u32)> {
+        self.history.borrow().iter().copied().collect()
+    }
+
+    pub fn clear(&self) {
+        self.history.borrow_mut().clear();
+    }
+}
+
+fn recorded_expiration<'r, T, X>(
+    recorder: &'r DecisionRecorder,
+    current_tick: u32,
+    expiration: X,
+) -> impl FnOnce(&T) -> bool + 'r
+where
+    X: FnOnce(&T) -> bool + 'r,
+{
+    move |v: &T| {
+        let expired = expiration(v);
+
+        let decision = if expired {
+            CacheDecision::Expire
+        } else {
+            CacheDecision::Hit
+        };
+
+        recorder.record(decision, current_tick);
+
+        expired
+    }
+}
+
+pub trait FastCacheRecordedAccessor<'a, T, R>: FastCacheAccessor<'a, T, R> {
+    #[allow(clippy::type_complexity)]
+    fn recorded_access<'r, X, F>(
+        self,
+        recorder: &'r DecisionRecorder,
+        current_tick: u32,
+        expiration: X,
+        filler: F,
+    ) -> CacheAccesor<'a, T, Self, impl FnOnce(&T) -> bool + 'r, impl FnOnce() -> T + 'r, R>
+    where
+        F: FnOnce() -> T + 'r,
+        X: FnOnce(&T) -> bool + 'r;
+}
+
+impl<'a, C, T, R> FastCacheRecordedAccessor<'a, T, R> for C
+where
+    C: FastCacheAccessor<'a, T, R>,
+{
+    #[allow(clippy::type_complexity)]
+    fn recorded_access<'r, X, F>(
+        self,
+        recorder: &'r DecisionRecorder,
+        current_tick: u32,
+        expiration: X,
+        filler: F,
+    ) -> CacheAccesor<'a, T, Self, impl FnOnce(&T) -> bool + 'r, impl FnOnce() -> T + 'r, R>
+    where
+        F: FnOnce() -> T + 'r,
+        X: FnOnce(&T) -> bool + 'r,
+    {
+        self.access(
+            recorded_expiration(recorder, current_tick, expiration),
+            move || {
+                recorder.record(CacheDecision::Miss, current_tick);
+
+                filler()
+            },
+        )
+    }
+}
+
+pub trait FastCacheRecordedMaybeAccessor<'a, T, R>: FastCacheMaybeAccessor<'a, T, R> {
+    #[allow(clippy::type_complexity)]
+    fn recorded_maybe_access<'r, X, F>(
+        self,
+        recorder: &'r DecisionRecorder,
+        current_tick: u32,
+        expiration: X,
+        filler: F,
+    ) -> MaybeCacheAccesor<
+        'a,
+        T,
+        Self,
+        impl FnOnce(&T) -> bool + 'r,
+        impl FnOnce() -> Option<T> + 'r,
+        R,
+    >
+    where
+        F: FnOnce() -> Option<T> + 'r,
+        X: FnOnce(&T) -> bool + 'r;
+}
+
+impl<'a, C, T, R> FastCacheRecordedMaybeAccessor<'a, T, R> for C
+where
+    C: FastCacheMaybeAccessor<'a, T, R>,
+{
+    #[allow(clippy::type_complexity)]
+    fn recorded_maybe_access<'r, X, F>(
+        self,
+        recorder: &'r DecisionRecorder,
+        current_tick: u32,
+        expiration: X,
+        filler: F,
+    ) -> MaybeCacheAccesor<
+        'a,
+        T,
+        Self,
+        impl FnOnce(&T) -> bool + 'r,
+        impl FnOnce() -> Option<T> + 'r,
+        R,
+    >
+    where
+        F: FnOnce() -> Option<T> + 'r,
+        X: FnOnce(&T) -> bool + 'r,
+    {
+        self.maybe_access(
+            recorded_expiration(recorder, current_tick, expiration),
+            move || {
+                recorder.record(CacheDecision::Miss, current_tick);
+
+                filler()
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_a_known_operation_sequence() {
+        use CacheDecision::*;
+
+        let recorder = DecisionRecorder::new(8);
+        let mut cache = None;
+
+        for tick in 0..4u32 {
+            (&mut cache)
+                .recorded_access(&recorder, tick, |v: &u32| tick - v >= 2, move || tick)
+                .take();
+        }
+
+        assert_eq!(
+            recorder.history(),
+            vec![(Miss, 0), (Hit, 1), (Expire, 2), (Miss, 2), (Hit, 3)]
+        );
+    }
+
+    #[test]
+    fn history_is_a_ring_buffer() {
+        let recorder = DecisionRecorder::new(2);
+
+        recorder.record(CacheDecision::Miss, 0);
+        recorder.record(CacheDecision::Hit, 1);
+        recorder.record(CacheDecision::Hit, 2);
+
+        assert_eq!(
+            recorder.history(),
+            vec![(CacheDecision::Hit, 1), (CacheDecision::Hit, 2)]
+        );
+
+        recorder.clear();
+
+        assert!(recorder.history().is_empty());
+    }
+
+    #[test]
+    fn maybe_accessors_record_empty_fills_as_misses() {
+        let recorder = DecisionRecorder::new(4);
+        let mut cache: Option<u32> = None;
+
+        (&mut cache)
+            .recorded_maybe_access(&recorder, 5, |_| false, || None)
+            .take();
+
+        assert_eq!(recorder.history(), vec![(CacheDecision::Miss, 5)]);
+    }
+}
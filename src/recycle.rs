@@ -0,0 +1,261 @@
+use super::*;
+
+pub trait FastCacheEvict<T>
+where
+    Self: Sized,
+{
+    fn evict_with<X>(self, expiration: X) -> (Self, Option<T>)
+    where
+        X: FnOnce(&T) -> bool;
+}
+
+impl<T> FastCacheEvict<T> for &mut Option<T> {
+    fn evict_with<X>(self, expiration: X) -> (Self, Option<T>)
+    where
+        X: FnOnce(&T) -> bool,
+    {
+        let old = if self.as_ref().map(expiration).unwrap_or(false) {
+            self.take()
+        } else {
+            None
+        };
+
+        (self, old)
+    }
+}
+
+impl<T> FastCacheEvict<T> for &RefCell<Option<T>> {
+    fn evict_with<X>(self, expiration: X) -> (Self, Option<T>)
+    where
+        X: FnOnce(&T) -> bool,
+    {
+        let expired = self.borrow().as_ref().map(expiration).unwrap_or(false);
+
+        let old = if expired {
+            self.borrow_mut().take()
+        } else {
+            None
+        };
+
+        (self, old)
+    }
+}
+
+impl<T> FastCacheEvict<T> for &mut OwnedCache<T> {
+    fn evict_with<X>(self, expiration: X) -> (Self, Option<T>)
+    where
+        X: FnOnce(&T) -> bool,
+    {
+        let old = if self.as_option().map(expiration).unwrap_or(false) {
+            self.clear()
+        } else {
+            None
+        };
+
+        (self, old)
+    }
+}
+
+pub trait FastCacheWithOldAccessor<'a, T, R>: FastCacheEvict<T> + FastCacheGet<'a, T, R> {
+    fn access_with_old<X, F>(
+        self,
+        expiration: X,
+        filler: F,
+    ) -> CacheWithOldAccesor<'a, T, Self, X, F, R>
+    where
+        F: FnOnce(Option<T>) -> T,
+        X: FnOnce(&T) -> bool;
+}
+
+impl<'a, C, T, R> FastCacheWithOldAccessor<'a, T, R> for C
+where
+    C: FastCacheEvict<T> + FastCacheGet<'a, T, R>,
+{
+    fn access_with_old<X, F>(
+        self,
+        expiration: X,
+        filler: F,
+    ) -> CacheWithOldAccesor<'a, T, Self, X, F, R>
+    where
+        F: FnOnce(Option<T>) -> T,
+        X: FnOnce(&T) -> bool,
+    {
+        CacheWithOldAccesor {
+            state: CacheWithOldState::Unknown {
+                cache: self,
+                expiration,
+                fill: filler,
+            },
+            phantom: PhantomData,
+        }
+    }
+}
+
+pub struct CacheWithOldAccesor<'c, T, C, X, F, R>
+where
+    F: FnOnce(Option<T>) -> T,
+    X: FnOnce(&T) -> bool,
+    C: FastCacheEvict<T> + FastCacheGet<'c, T, R>,
+{
+    state: CacheWithOldState<C, X, F, R>,
+    phantom: PhantomData<(&'c C, T)>,
+}
+
+pub enum CacheWithOldState<C, X, F, R> {
+    Unknown { cache: C, expiration: X, fill: F },
+    Known(R),
+}
+
+impl<C, X, F, R> CacheWithOldState<C, X, F, R> {
+    pub fn into_known<'c, T>(self) -> Self
+    where
+        F: FnOnce(Option<T>) -> T,
+        X: FnOnce(&T) -> bool,
+        C: FastCacheEvict<T> + FastCacheGet<'c, T, R>,
+    {
+        match self {
+            CacheWithOldState::Unknown {
+                cache,
+                expiration,
+                fill,
+            } => {
+                let (cache, old) = cache.evict_with(expiration);
+
+                CacheWithOldState::Known(
+                    cache.get_or_insert_with(move || cold_fill(move || fill(old))),
+                )
+            }
+            v => v,
+        }
+    }
+}
+
+impl<'c, T, C, X, F, R> Get<R> for CacheWithOldAccesor<'c, T, C, X, F, R>
+where
+    F: FnOnce(Option<T>) -> T,
+    X: FnOnce(&T) -> bool,
+    C: FastCacheEvict<T> + FastCacheGet<'c, T, R>,
+{
+    fn get(&mut self) -> &R {
+        take_mut::take(&mut self.state, |v| v.into_known());
+
+        match &self.state {
+            CacheWithOldState::Unknown { .. } => unsafe { std::hint::unreachable_unchecked() },
+            CacheWithOldState::Known(data) => data,
+        }
+    }
+
+    fn take(self) -> R {
+        match self.state.into_known() {
+            CacheWithOldState::Unknown { .. } => unsafe { std::hint::unreachable_unchecked() },
+            CacheWithOldState::Known(data) => data,
+        }
+    }
+}
+
+pub trait FastCacheCollectAccessor<'a, I, R>: FastCacheWithOldAccessor<'a, Vec<I>, R> {
+    #[allow(clippy::type_complexity)]
+    fn access_collect<X, S>(
+        self,
+        expiration: X,
+        source: S,
+    ) -> CacheWithOldAccesor<'a, Vec<I>, Self, X, impl FnOnce(Option<Vec<I>>) -> Vec<I>, R>
+    where
+        S: IntoIterator<Item = I>,
+        X: FnOnce(&Vec<I>) -> bool;
+}
+
+impl<'a, C, I, R> FastCacheCollectAccessor<'a, I, R> for C
+where
+    C: FastCacheWithOldAccessor<'a, Vec<I>, R>,
+{
+    #[allow(clippy::type_complexity)]
+    fn access_collect<X, S>(
+        self,
+        expiration: X,
+        source: S,
+    ) -> CacheWithOldAccesor<'a, Vec<I>, Self, X, impl FnOnce(Option<Vec<I>>) -> Vec<I>, R>
+    where
+        S: IntoIterator<Item = I>,
+        X: FnOnce(&Vec<I>) -> bool,
+    {
+        self.access_with_old(expiration, move |old: Option<Vec<I>>| {
+            let mut items = old.unwrap_or_default();
+
+            items.clear();
+            items.extend(source);
+
+            items
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn access_collect_consumes_the_source_once_per_fill() {
+        let mut cache: Option<Vec<u32>> = None;
+        let pulled = Cell::new(0);
+
+        let source = (0..4).inspect(|_| pulled.set(pulled.get() + 1));
+        assert_eq!(
+            *(&mut cache).access_collect(|_| false, source).take(),
+            vec![0, 1, 2, 3]
+        );
+        assert_eq!(pulled.get(), 4);
+
+        let source = (0..4).inspect(|_| pulled.set(pulled.get() + 1));
+        assert_eq!(
+            (&mut cache).access_collect(|_| false, source).get().len(),
+            4
+        );
+        assert_eq!(pulled.get(), 4);
+
+        let capacity = cache.as_ref().unwrap().capacity();
+        let source = (7..9).inspect(|_| pulled.set(pulled.get() + 1));
+        assert_eq!(
+            *(&mut cache).access_collect(|_| true, source).take(),
+            vec![7, 8]
+        );
+        assert_eq!(pulled.get(), 6);
+        assert_eq!(cache.as_ref().unwrap().capacity(), capacity);
+    }
+
+    #[test]
+    fn evict_with_on_refcell_only_takes_expired_values() {
+        let cache = RefCell::new(Some(5));
+
+        let (_, old) = (&cache).evict_with(|v| *v > 10);
+        assert_eq!(old, None);
+        assert_eq!(*cache.borrow(), Some(5));
+
+        let (_, old) = (&cache).evict_with(|v| *v == 5);
+        assert_eq!(old, Some(5));
+        assert_eq!(*cache.borrow(), None);
+    }
+
+    #[test]
+    fn refcell_access_with_old_hands_the_evicted_value_to_the_filler() {
+        let cache = RefCell::new(Some(vec![1, 2]));
+
+        let held = cache.borrow();
+        let (_, old) = (&cache).evict_with(|v: &Vec<u32>| v.is_empty());
+        assert_eq!(old, None);
+        drop(held);
+
+        let value = (&cache)
+            .access_with_old(
+                |v| v.len() == 2,
+                |old| {
+                    let mut items = old.unwrap_or_default();
+                    items.push(3);
+                    items
+                },
+            )
+            .take();
+
+        assert_eq!(*value, vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,154 @@
+use super::*;
+
+pub struct ReusableAccessor<T, C, X, F>
+where
+    X: Fn(&T) -> bool,
+    F: Fn() -> T,
+{
+    cache: C,
+    expiration: X,
+    fill: F,
+    known: bool,
+    uncached: bool,
+    phantom: PhantomData<T>,
+}
+
+impl<T, C, X, F> ReusableAccessor<T, C, X, F>
+where
+    X: Fn(&T) -> bool,
+    F: Fn() -> T,
+{
+    pub fn new(cache: C, expiration: X, filler: F) -> Self {
+        ReusableAccessor {
+            cache,
+            expiration,
+            fill: filler,
+            known: false,
+            uncached: false,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn cache(&self) -> &C {
+        &self.cache
+    }
+
+    pub fn into_cache(self) -> C {
+        self.cache
+    }
+
+    pub fn is_known(&self) -> bool {
+        self.known
+    }
+
+    pub fn uncache(&mut self) {
+        self.known = false;
+        self.uncached = true;
+    }
+
+    pub fn get(&mut self) -> &T
+    where
+        for<'a> &'a mut C: FastCacheGet<'a, T, &'a T> + FastCacheExpiration<T>,
+    {
+        let expiration = &self.expiration;
+        let uncached = std::mem::replace(&mut self.uncached, false);
+
+        self.known = true;
+
+        (&mut self.cache)
+            .access(move |v: &T| expiration(v) || uncached, &self.fill)
+            .take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reapplies_expiration_every_tick() {
+        let tick = Cell::new(0);
+        let fills = Cell::new(0);
+
+        let mut accessor = ReusableAccessor::new(
+            None,
+            |v: &(u32, u32)| v.0 != tick.get(),
+            || {
+                fills.set(fills.get() + 1);
+                (tick.get(), fills.get())
+            },
+        );
+
+        assert_eq!(accessor.get().1, 1);
+        assert_eq!(accessor.get().1, 1);
+
+        tick.set(1);
+
+        assert_eq!(accessor.get().1, 2);
+        assert_eq!(accessor.get().1, 2);
+        assert!(accessor.is_known());
+    }
+
+    #[test]
+    fn uncache_forces_a_fresh_fill() {
+        let fills = Cell::new(0);
+
+        let mut accessor = ReusableAccessor::new(
+            None,
+            |_: &u32| false,
+            || {
+                fills.set(fills.get() + 1);
+                fills.get()
+            },
+        );
+
+        assert_eq!(*accessor.get(), 1);
+
+        accessor.uncache();
+
+        assert!(!accessor.is_known());
+        assert_eq!(*accessor.get(), 2);
+        assert_eq!(*accessor.get(), 2);
+    }
+
+    type Stamped = (u32, u32);
+
+    struct Room<'t, X, F>
+    where
+        X: Fn(&Stamped) -> bool,
+        F: Fn() -> Stamped,
+    {
+        tick: &'t Cell<u32>,
+        energy: ReusableAccessor<Stamped, Option<Stamped>, X, F>,
+    }
+
+    #[test]
+    fn one_accessor_in_a_struct_field_serves_many_ticks() {
+        let tick = Cell::new(0);
+        let fills = Cell::new(0);
+        let (tick_ref, fills_ref) = (&tick, &fills);
+
+        let mut room = Room {
+            tick: &tick,
+            energy: ReusableAccessor::new(
+                None,
+                move |v: &Stamped| tick_ref.get() - v.0 >= 2,
+                move || {
+                    fills_ref.set(fills_ref.get() + 1);
+                    (tick_ref.get(), tick_ref.get() * 100)
+                },
+            ),
+        };
+
+        let mut seen = Vec::new();
+
+        for t in 0..6 {
+            room.tick.set(t);
+            seen.push(room.energy.get().1);
+        }
+
+        assert_eq!(seen, vec![0, 0, 200, 200, 400, 400]);
+        assert_eq!(fills.get(), 3);
+        assert_eq!(room.energy.into_cache(), Some((4, 400)));
+    }
+}
@@ -0,0 +1,146 @@
+use super::*;
+
+pub trait RefreshScheduler {
+    fn should_refresh(&self) -> bool;
+}
+
+impl<S> RefreshScheduler for S
+where
+    S: Fn() -> bool,
+{
+    fn should_refresh(&self) -> bool {
+        self()
+    }
+}
+
+pub trait FastCacheScheduledAccessor<'a, T, R>: FastCacheAccessor<'a, T, R> {
+    fn scheduled_access<'s, S, X, F>(
+        self,
+        scheduler: &'s S,
+        expiration: X,
+        filler: F,
+    ) -> CacheAccesor<'a, T, Self, impl FnOnce(&T) -> bool + 's, F, R>
+    where
+        S: RefreshScheduler + ?Sized,
+        F: FnOnce() -> T,
+        X: FnOnce(&T) -> bool + 's;
+}
+
+impl<'a, C, T, R> FastCacheScheduledAccessor<'a, T, R> for C
+where
+    C: FastCacheAccessor<'a, T, R>,
+{
+    fn scheduled_access<'s, S, X, F>(
+        self,
+        scheduler: &'s S,
+        expiration: X,
+        filler: F,
+    ) -> CacheAccesor<'a, T, Self, impl FnOnce(&T) -> bool + 's, F, R>
+    where
+        S: RefreshScheduler + ?Sized,
+        F: FnOnce() -> T,
+        X: FnOnce(&T) -> bool + 's,
+    {
+        self.access(
+            move |v: &T| expiration(v) && scheduler.should_refresh(),
+            filler,
+        )
+    }
+}
+
+pub trait FastCacheScheduledMaybeAccessor<'a, T, R>: FastCacheMaybeAccessor<'a, T, R> {
+    fn scheduled_maybe_access<'s, S, X, F>(
+        self,
+        scheduler: &'s S,
+        expiration: X,
+        filler: F,
+    ) -> MaybeCacheAccesor<'a, T, Self, impl FnOnce(&T) -> bool + 's, F, R>
+    where
+        S: RefreshScheduler + ?Sized,
+        F: FnOnce() -> Option<T>,
+        X: FnOnce(&T) -> bool + 's;
+}
+
+impl<'a, C, T, R> FastCacheScheduledMaybeAccessor<'a, T, R> for C
+where
+    C: FastCacheMaybeAccessor<'a, T, R>,
+{
+    fn scheduled_maybe_access<'s, S, X, F>(
+        self,
+        scheduler: &'s S,
+        expiration: X,
+        filler: F,
+    ) -> MaybeCacheAccesor<'a, T, Self, impl FnOnce(&T) -> bool + 's, F, R>
+    where
+        S: RefreshScheduler + ?Sized,
+        F: FnOnce() -> Option<T>,
+        X: FnOnce(&T) -> bool + 's,
+    {
+        self.maybe_access(
+            move |v: &T| expiration(v) && scheduler.should_refresh(),
+            filler,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Budget(Cell<u32>);
+
+    impl RefreshScheduler for Budget {
+        fn should_refresh(&self) -> bool {
+            let remaining = self.0.get();
+
+            self.0.set(remaining.saturating_sub(1));
+
+            remaining > 0
+        }
+    }
+
+    #[test]
+    fn vetoed_refills_keep_stale_values() {
+        let budget = Budget(Cell::new(1));
+        let mut caches = [Some(0), Some(0), Some(0)];
+
+        let values: Vec<u32> = caches
+            .iter_mut()
+            .map(|cache| *cache.scheduled_access(&budget, |_| true, || 1).take())
+            .collect();
+
+        assert_eq!(values, vec![1, 0, 0]);
+    }
+
+    #[test]
+    fn empty_slots_fill_regardless_of_the_scheduler() {
+        let never = || false;
+        let mut cache = None;
+
+        assert_eq!(
+            *(&mut cache).scheduled_access(&never, |_| true, || 4).take(),
+            4
+        );
+    }
+
+    #[test]
+    fn maybe_accessors_consult_the_scheduler() {
+        let always = || true;
+        let never = || false;
+        let mut cache = Some(1);
+
+        let kept = (&mut cache)
+            .scheduled_maybe_access(&never, |_| true, || Some(2))
+            .take()
+            .copied();
+
+        assert_eq!(kept, Some(1));
+
+        let refreshed = (&mut cache)
+            .scheduled_maybe_access(&always, |_| true, || Some(3))
+            .take()
+            .copied();
+
+        assert_eq!(refreshed, Some(3));
+    }
+}
@@ -0,0 +1,88 @@
+use super::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+impl<'c, T, C, X, F, R> CacheAccesor<'c, T, C, X, F, R>
+where
+    F: FnOnce() -> T,
+    X: FnOnce(&T) -> bool,
+    C: FastCacheGet<'c, T, R> + FastCacheExpiration<T>,
+{
+    pub fn to_json(&self) -> Option<Value>
+    where
+        R: Deref,
+        R::Target: Serialize,
+    {
+        match &self.state {
+            CacheState::Unknown(_, _) => None,
+            CacheState::Known(s) => serde_json::to_value(&*s.data).ok(),
+        }
+    }
+}
+
+impl<'c, T, C, R> CacheAccesor<'c, T, C, fn(&T) -> bool, fn() -> T, R>
+where
+    C: FastCacheGet<'c, T, R> + FastCacheExpiration<T>,
+{
+    pub fn from_known_json(cache: C, value: Value) -> Result<Self, serde_json::Error>
+    where
+        T: DeserializeOwned,
+    {
+        let value: T = serde_json::from_value(value)?;
+
+        let data = cache
+            .expire_with(|_| true)
+            .get_or_insert_with(move || value);
+
+        Ok(CacheAccesor {
+            state: CacheState::Known(CacheStateKnown { data }),
+            phantom: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn unresolved_accessor_has_no_json() {
+        let mut cache: Option<u32> = None;
+
+        let accessor = (&mut cache).access(|_| false, || 1);
+
+        assert_eq!(accessor.to_json(), None);
+    }
+
+    #[test]
+    fn round_trips_through_the_backend() {
+        let mut saved: Option<Vec<u32>> = None;
+
+        let json = {
+            let mut accessor = (&mut saved).access(|_| false, || vec![1, 2, 3]);
+
+            accessor.get();
+            accessor.to_json().unwrap()
+        };
+
+        assert_eq!(json, json!([1, 2, 3]));
+
+        let mut restored: Option<Vec<u32>> = Some(vec![9]);
+
+        let accessor = CacheAccesor::from_known_json(&mut restored, json).unwrap();
+
+        assert_eq!(accessor.to_json(), Some(json!([1, 2, 3])));
+        assert_eq!(*accessor.take(), vec![1, 2, 3]);
+        assert_eq!(restored, Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn rejects_mismatched_json() {
+        let cache = RefCell::new(None::<u32>);
+
+        assert!(CacheAccesor::from_known_json(&cache, json!("nope")).is_err());
+        assert!(cache.borrow().is_none());
+    }
+}
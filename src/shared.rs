@@ -0,0 +1,62 @@
+use super::*;
+
+#[derive(Clone, Copy)]
+pub struct SharedCache<R> {
+    data: R,
+}
+
+impl<R> SharedCache<R> {
+    pub fn into_inner(self) -> R {
+        self.data
+    }
+}
+
+impl<R> Deref for SharedCache<R>
+where
+    R: Deref,
+{
+    type Target = R::Target;
+
+    fn deref(&self) -> &R::Target {
+        &self.data
+    }
+}
+
+impl<'c, T, C, X, F, R> CacheAccesor<'c, T, C, X, F, R>
+where
+    F: FnOnce() -> T,
+    X: FnOnce(&T) -> bool,
+    C: FastCacheGet<'c, T, R> + FastCacheExpiration<T>,
+{
+    pub fn freeze(self) -> SharedCache<R> {
+        SharedCache { data: self.take() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frozen_handles_share_one_fill() {
+        let mut cache = None;
+        let fills = Cell::new(0);
+
+        let frozen = (&mut cache)
+            .access(
+                |_| false,
+                || {
+                    fills.set(fills.get() + 1);
+                    vec![1, 2, 3]
+                },
+            )
+            .freeze();
+
+        let readers = [frozen, frozen, frozen];
+
+        assert!(readers.iter().all(|r| r.len() == 3));
+        assert_eq!(readers.iter().map(|r| r[1]).sum::<i32>(), 6);
+        assert_eq!(fills.get(), 1);
+        assert_eq!(frozen.into_inner(), &vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,154 @@
+use super::*;
+
+pub trait FastCacheSkipFillAccessor<'a, T, R>:
+    FastCacheExpiration<T> + FastCacheMaybeGet<'a, T, R> + Sized
+{
+    fn expire_or_skip_fill<X, F>(
+        self,
+        predicate: X,
+        filler: F,
+    ) -> SkipFillCacheAccesor<'a, T, Self, X, F, R>
+    where
+        F: FnOnce() -> T,
+        X: Fn(Option<&T>) -> bool;
+}
+
+impl<'a, C, T, R> FastCacheSkipFillAccessor<'a, T, R> for C
+where
+    C: FastCacheExpiration<T> + FastCacheMaybeGet<'a, T, R>,
+{
+    fn expire_or_skip_fill<X, F>(
+        self,
+        predicate: X,
+        filler: F,
+    ) -> SkipFillCacheAccesor<'a, T, Self, X, F, R>
+    where
+        F: FnOnce() -> T,
+        X: Fn(Option<&T>) -> bool,
+    {
+        SkipFillCacheAccesor {
+            state: SkipFillCacheState::Unknown {
+                cache: self,
+                predicate,
+                fill: filler,
+            },
+            phantom: PhantomData,
+        }
+    }
+}
+
+pub struct SkipFillCacheAccesor<'c, T, C, X, F, R>
+where
+    F: FnOnce() -> T,
+    X: Fn(Option<&T>) -> bool,
+    C: FastCacheExpiration<T> + FastCacheMaybeGet<'c, T, R>,
+{
+    state: SkipFillCacheState<C, X, F, R>,
+    phantom: PhantomData<(&'c C, T)>,
+}
+
+pub enum SkipFillCacheState<C, X, F, R> {
+    Unknown { cache: C, predicate: X, fill: F },
+    Known(Option<R>),
+}
+
+impl<C, X, F, R> SkipFillCacheState<C, X, F, R> {
+    pub fn into_known<'c, T>(self) -> Self
+    where
+        F: FnOnce() -> T,
+        X: Fn(Option<&T>) -> bool,
+        C: FastCacheExpiration<T> + FastCacheMaybeGet<'c, T, R>,
+    {
+        match self {
+            SkipFillCacheState::Unknown {
+                cache,
+                predicate,
+                fill,
+            } => {
+                let mut was_present = false;
+
+                let cache = cache.expire_with(|v| {
+                    was_present = true;
+
+                    predicate(Some(v))
+                });
+
+                SkipFillCacheState::Known(cache.maybe_get_or_insert_with(move || {
+                    if !was_present && predicate(None) {
+                        None
+                    } else {
+                        Some(cold_fill(fill))
+                    }
+                }))
+            }
+            v => v,
+        }
+    }
+}
+
+impl<'c, T, C, X, F, R> MaybeGet<R> for SkipFillCacheAccesor<'c, T, C, X, F, R>
+where
+    F: FnOnce() -> T,
+    X: Fn(Option<&T>) -> bool,
+    C: FastCacheExpiration<T> + FastCacheMaybeGet<'c, T, R>,
+{
+    fn get(&mut self) -> Option<&R> {
+        take_mut::take(&mut self.state, |v| v.into_known());
+
+        match &self.state {
+            SkipFillCacheState::Unknown { .. } => unsafe { std::hint::unreachable_unchecked() },
+            SkipFillCacheState::Known(data) => data.as_ref(),
+        }
+    }
+
+    fn take(self) -> Option<R> {
+        match self.state.into_known() {
+            SkipFillCacheState::Unknown { .. } => unsafe { std::hint::unreachable_unchecked() },
+            SkipFillCacheState::Known(data) => data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_slots_can_stay_empty() {
+        let mut cache: Option<u32> = None;
+        let fills = Cell::new(0);
+        let fill = || {
+            fills.set(fills.get() + 1);
+            4
+        };
+
+        let skip_empty = |v: Option<&u32>| v.is_none();
+        assert!((&mut cache)
+            .expire_or_skip_fill(skip_empty, fill)
+            .take()
+            .is_none());
+        assert_eq!(cache, None);
+        assert_eq!(fills.get(), 0);
+
+        let fill_empty = |v: Option<&u32>| v.is_some_and(|v| *v > 10);
+        assert_eq!(
+            (&mut cache).expire_or_skip_fill(fill_empty, fill).take(),
+            Some(&4)
+        );
+        assert_eq!(fills.get(), 1);
+    }
+
+    #[test]
+    fn present_values_expire_and_refill() {
+        let mut cache = Some(3);
+
+        let mut accessor = (&mut cache).expire_or_skip_fill(|v| v == Some(&3), || 5);
+        assert_eq!(accessor.get(), Some(&&5));
+        assert_eq!(accessor.take(), Some(&5));
+
+        let kept = (&mut cache)
+            .expire_or_skip_fill(|v| v.is_none(), || 6)
+            .take();
+        assert_eq!(kept, Some(&5));
+    }
+}
@@ -0,0 +1,47 @@
+use super::*;
+use std::slice::SliceIndex;
+
+impl<'c, T, C, X, F, R> CacheAccesor<'c, T, C, X, F, R>
+where
+    F: FnOnce() -> T,
+    X: FnOnce(&T) -> bool,
+    C: FastCacheGet<'c, T, R> + FastCacheExpiration<T>,
+    R: Deref,
+{
+    pub fn get_range<E, I>(&mut self, range: I) -> Result<&[E], CacheError>
+    where
+        R::Target: AsRef<[E]>,
+        I: SliceIndex<[E], Output = [E]>,
+    {
+        self.get()
+            .deref()
+            .as_ref()
+            .get(range)
+            .ok_or(CacheError::OutOfRange)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_range_slices_valid_ranges() {
+        let mut cache = None;
+        let mut accessor = (&mut cache).access(|_| false, || vec![1u8, 2, 3, 4]);
+
+        assert_eq!(accessor.get_range(1..3), Ok(&[2u8, 3][..]));
+        assert_eq!(accessor.get_range(..), Ok(&[1u8, 2, 3, 4][..]));
+        assert_eq!(accessor.get_range(4..), Ok(&[][..]));
+    }
+
+    #[test]
+    fn get_range_reports_out_of_range() {
+        let cache = RefCell::new(None);
+        let mut accessor = (&cache).access(|_| false, || b"blob".to_vec());
+
+        assert_eq!(accessor.get_range(2..9), Err(CacheError::OutOfRange));
+        assert_eq!(accessor.get_range(5..), Err(CacheError::OutOfRange));
+        assert_eq!(accessor.get_range(..2), Ok(&b"bl"[..]));
+    }
+}
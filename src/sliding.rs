@@ -0,0 +1,149 @@
+use super::*;
+
+pub struct SlidingStamped<T> {
+    pub value: T,
+    last_access: Cell<u32>,
+}
+
+impl<T> SlidingStamped<T> {
+    pub fn new(value: T, tick: u32) -> Self {
+        SlidingStamped {
+            value,
+            last_access: Cell::new(tick),
+        }
+    }
+
+    pub fn last_access(&self) -> u32 {
+        self.last_access.get()
+    }
+
+    pub fn touch(&self, current_tick: u32) {
+        self.last_access.set(current_tick);
+    }
+
+    pub fn is_idle_for(&self, current_tick: u32, ttl: u32) -> bool {
+        current_tick.wrapping_sub(self.last_access.get()) >= ttl
+    }
+}
+
+impl<'c, T, C, X, F, R> CacheAccesor<'c, SlidingStamped<T>, C, X, F, R>
+where
+    F: FnOnce() -> SlidingStamped<T>,
+    X: FnOnce(&SlidingStamped<T>) -> bool,
+    C: FastCacheGet<'c, SlidingStamped<T>, R>
+        + FastCacheExpiration<SlidingStamped<T>>
+        + FastCachePeek<SlidingStamped<T>>,
+    R: Deref<Target = SlidingStamped<T>>,
+{
+    pub fn touch(&mut self, current_tick: u32) -> bool {
+        match &self.state {
+            CacheState::Unknown(state, _) => state
+                .cache
+                .with_stored(|v: &SlidingStamped<T>| v.touch(current_tick))
+                .is_some(),
+            CacheState::Known(s) => {
+                s.data.touch(current_tick);
+
+                true
+            }
+        }
+    }
+}
+
+pub trait FastCacheSlidingAccessor<'a, T, R>: FastCacheAccessor<'a, SlidingStamped<T>, R> {
+    #[allow(clippy::type_complexity)]
+    fn access_sliding<F>(
+        self,
+        current_tick: u32,
+        ttl: u32,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        SlidingStamped<T>,
+        Self,
+        impl FnOnce(&SlidingStamped<T>) -> bool,
+        impl FnOnce() -> SlidingStamped<T>,
+        R,
+    >
+    where
+        F: FnOnce() -> T;
+}
+
+impl<'a, C, T, R> FastCacheSlidingAccessor<'a, T, R> for C
+where
+    C: FastCacheAccessor<'a, SlidingStamped<T>, R>,
+{
+    #[allow(clippy::type_complexity)]
+    fn access_sliding<F>(
+        self,
+        current_tick: u32,
+        ttl: u32,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        SlidingStamped<T>,
+        Self,
+        impl FnOnce(&SlidingStamped<T>) -> bool,
+        impl FnOnce() -> SlidingStamped<T>,
+        R,
+    >
+    where
+        F: FnOnce() -> T,
+    {
+        self.access(
+            move |v: &SlidingStamped<T>| {
+                if v.is_idle_for(current_tick, ttl) {
+                    return true;
+                }
+
+                v.touch(current_tick);
+
+                false
+            },
+            move || SlidingStamped::new(filler(), current_tick),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn touch_prevents_a_due_expiry() {
+        let mut cache = Some(SlidingStamped::new(1, 0));
+
+        assert!((&mut cache).access_sliding(8, 10, || 2).touch(8));
+
+        let value = (&mut cache).access_sliding(15, 10, || 2).take().value;
+
+        assert_eq!(value, 1);
+        assert_eq!(cache.as_ref().map(|v| v.last_access()), Some(15));
+    }
+
+    #[test]
+    fn untouched_values_expire() {
+        let mut cache = Some(SlidingStamped::new(1, 0));
+
+        let value = (&mut cache).access_sliding(10, 10, || 2).take().value;
+
+        assert_eq!(value, 2);
+    }
+
+    #[test]
+    fn touch_reports_missing_or_borrowed_values() {
+        let empty: RefCell<Option<SlidingStamped<u32>>> = RefCell::new(None);
+
+        assert!(!(&empty).access_sliding(1, 10, || 2).touch(1));
+
+        let cell = RefCell::new(Some(SlidingStamped::new(1, 0)));
+        let held = cell.borrow_mut();
+
+        assert!(!(&cell).access_sliding(1, 10, || 2).touch(1));
+
+        drop(held);
+
+        assert!((&cell).access_sliding(1, 10, || 2).touch(1));
+        assert_eq!(cell.borrow().as_ref().map(|v| v.last_access()), Some(1));
+    }
+}
@@ -0,0 +1,108 @@
+use super::*;
+
+pub fn expire_all<T, X>(slots: &mut [Option<T>], predicate: X)
+where
+    X: Fn(&T) -> bool,
+{
+    for slot in slots.iter_mut() {
+        slot.expire_with(&predicate);
+    }
+}
+
+#[derive(Default)]
+pub struct Slot<C> {
+    cache: C,
+}
+
+pub type SlotCache<T> = Slot<Option<T>>;
+
+pub type SharedSlotCache<T> = Slot<RefCell<Option<T>>>;
+
+impl<T> Slot<Option<T>> {
+    pub fn new() -> Self {
+        Slot { cache: None }
+    }
+
+    pub fn clear(&mut self) -> Option<T> {
+        self.cache.take()
+    }
+
+    pub fn get_or_insert_with<X, F>(&mut self, expiration: X, filler: F) -> &T
+    where
+        X: FnOnce(&T) -> bool,
+        F: FnOnce() -> T,
+    {
+        (&mut self.cache).access(expiration, filler).take()
+    }
+}
+
+impl<T> Slot<RefCell<Option<T>>> {
+    pub fn new() -> Self {
+        Slot {
+            cache: RefCell::new(None),
+        }
+    }
+
+    pub fn clear(&self) -> Option<T> {
+        self.cache.borrow_mut().take()
+    }
+
+    pub fn get_or_insert_with<X, F>(&self, expiration: X, filler: F) -> Ref<'_, T>
+    where
+        X: FnOnce(&T) -> bool,
+        F: FnOnce() -> T,
+    {
+        self.cache.access(expiration, filler).take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expire_all_clears_only_expired_slots() {
+        let mut slots = [Some(1), None, Some(4), Some(7), Some(2)];
+
+        expire_all(&mut slots, |v| *v % 2 == 0);
+
+        assert_eq!(slots, [Some(1), None, None, Some(7), None]);
+
+        expire_all(&mut slots, |_| false);
+        assert_eq!(slots, [Some(1), None, None, Some(7), None]);
+    }
+
+    struct Planner {
+        paths: SlotCache<Vec<u32>>,
+        targets: SharedSlotCache<u32>,
+    }
+
+    #[test]
+    fn slot_caches_are_storable_in_struct_fields() {
+        let mut planner = Planner {
+            paths: SlotCache::new(),
+            targets: SharedSlotCache::new(),
+        };
+
+        assert_eq!(
+            planner.paths.get_or_insert_with(|_| false, || vec![1, 2]),
+            &[1, 2]
+        );
+        assert_eq!(
+            planner.paths.get_or_insert_with(|_| false, || vec![3]),
+            &[1, 2]
+        );
+        assert_eq!(
+            planner
+                .paths
+                .get_or_insert_with(|p| p.len() == 2, || vec![3]),
+            &[3]
+        );
+        assert_eq!(planner.paths.clear(), Some(vec![3]));
+
+        assert_eq!(*planner.targets.get_or_insert_with(|_| false, || 5), 5);
+        assert_eq!(*planner.targets.get_or_insert_with(|t| *t == 5, || 6), 6);
+        assert_eq!(planner.targets.clear(), Some(6));
+        assert_eq!(planner.targets.clear(), None);
+    }
+}
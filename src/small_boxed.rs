@@ -0,0 +1,126 @@
+use super::*;
+use smallbox::space::S4;
+use smallbox::{smallbox, SmallBox};
+
+trait DynTakeOnce<R> {
+    fn take_once(&mut self) -> R;
+}
+
+impl<A, R> DynTakeOnce<R> for Option<A>
+where
+    A: Get<R>,
+{
+    fn take_once(&mut self) -> R {
+        self.take().unwrap().take()
+    }
+}
+
+pub struct SmallBoxedAccessor<'a, R, S = S4> {
+    state: SmallBoxedAccessorState<'a, R, S>,
+}
+
+enum SmallBoxedAccessorState<'a, R, S> {
+    Pending(SmallBox<dyn DynTakeOnce<R> + 'a, S>),
+    Forced(R),
+}
+
+impl<'a, R, S> SmallBoxedAccessor<'a, R, S> {
+    pub fn new<A>(accessor: A) -> Self
+    where
+        A: Get<R> + 'a,
+    {
+        let pending: SmallBox<dyn DynTakeOnce<R> + 'a, S> = smallbox!(Some(accessor));
+
+        SmallBoxedAccessor {
+            state: SmallBoxedAccessorState::Pending(pending),
+        }
+    }
+
+    pub fn is_heap(&self) -> bool {
+        match &self.state {
+            SmallBoxedAccessorState::Pending(accessor) => accessor.is_heap(),
+            SmallBoxedAccessorState::Forced(_) => false,
+        }
+    }
+
+    pub fn is_forced(&self) -> bool {
+        matches!(self.state, SmallBoxedAccessorState::Forced(_))
+    }
+
+    pub fn peek(&self) -> Option<&R> {
+        match &self.state {
+            SmallBoxedAccessorState::Pending(_) => None,
+            SmallBoxedAccessorState::Forced(data) => Some(data),
+        }
+    }
+}
+
+impl<'a, R, S> Get<R> for SmallBoxedAccessor<'a, R, S> {
+    fn get(&mut self) -> &R {
+        take_mut::take(&mut self.state, |v| match v {
+            SmallBoxedAccessorState::Pending(mut accessor) => {
+                SmallBoxedAccessorState::Forced(accessor.take_once())
+            }
+            v => v,
+        });
+
+        match &self.state {
+            SmallBoxedAccessorState::Pending(_) => unsafe { std::hint::unreachable_unchecked() },
+            SmallBoxedAccessorState::Forced(data) => data,
+        }
+    }
+
+    fn take(self) -> R {
+        match self.state {
+            SmallBoxedAccessorState::Pending(mut accessor) => accessor.take_once(),
+            SmallBoxedAccessorState::Forced(data) => data,
+        }
+    }
+}
+
+pub trait IntoSmallBoxedAccessor<R>: Get<R> {
+    fn small_boxed<'a, S>(self) -> SmallBoxedAccessor<'a, R, S>
+    where
+        Self: Sized + 'a;
+}
+
+impl<A, R> IntoSmallBoxedAccessor<R> for A
+where
+    A: Get<R>,
+{
+    fn small_boxed<'a, S>(self) -> SmallBoxedAccessor<'a, R, S>
+    where
+        Self: Sized + 'a,
+    {
+        SmallBoxedAccessor::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_closures_stay_inline() {
+        let mut cache = None;
+
+        let mut accessor: SmallBoxedAccessor<'_, &u32> =
+            (&mut cache).access(|_| false, || 7).small_boxed();
+
+        assert!(!accessor.is_heap());
+        assert_eq!(**accessor.get(), 7);
+    }
+
+    #[test]
+    fn large_captures_spill_to_the_heap() {
+        let mut cache = None;
+        let payload = [1u64; 16];
+
+        let accessor: SmallBoxedAccessor<'_, &u64> = (&mut cache)
+            .access(|_| false, move || payload.iter().sum())
+            .small_boxed();
+
+        assert!(accessor.is_heap());
+        assert_eq!(*accessor.take(), 16);
+    }
+}
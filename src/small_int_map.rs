@@ -0,0 +1,122 @@
+use super::*;
+
+pub struct SmallIntCacheMap<V, const N: usize> {
+    entries: RefCell<[Option<V>; N]>,
+}
+
+impl<V, const N: usize> Default for SmallIntCacheMap<V, N> {
+    fn default() -> Self {
+        SmallIntCacheMap {
+            entries: RefCell::new(std::array::from_fn(|_| None)),
+        }
+    }
+}
+
+impl<V, const N: usize> SmallIntCacheMap<V, N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.borrow().iter().filter(|e| e.is_some()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.borrow().iter().all(|e| e.is_none())
+    }
+
+    fn check(key: usize) -> Result<usize, CacheError> {
+        if key < N {
+            Ok(key)
+        } else {
+            Err(CacheError::KeyOutOfRange { key, capacity: N })
+        }
+    }
+
+    pub fn get(&self, key: usize) -> Result<Option<Ref<'_, V>>, CacheError> {
+        let key = Self::check(key)?;
+
+        Ok(Ref::filter_map(self.entries.borrow(), |e| e[key].as_ref()).ok())
+    }
+
+    pub fn insert(&self, key: usize, value: V) -> Result<Option<V>, CacheError> {
+        let key = Self::check(key)?;
+
+        Ok(self.entries.borrow_mut()[key].replace(value))
+    }
+
+    pub fn remove(&self, key: usize) -> Result<Option<V>, CacheError> {
+        let key = Self::check(key)?;
+
+        Ok(self.entries.borrow_mut()[key].take())
+    }
+
+    pub fn clear(&self) {
+        for entry in self.entries.borrow_mut().iter_mut() {
+            *entry = None;
+        }
+    }
+
+    #[inline]
+    pub fn get_or_insert_with<F>(&self, key: usize, filler: F) -> Result<Ref<'_, V>, CacheError>
+    where
+        F: FnOnce() -> V,
+    {
+        let key = Self::check(key)?;
+
+        if self.entries.borrow()[key].is_none() {
+            let value = filler();
+
+            self.entries.borrow_mut()[key] = Some(value);
+        }
+
+        Ok(Ref::map(self.entries.borrow(), |e| {
+            e[key].as_ref().unwrap()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_each_slot_once() {
+        let map: SmallIntCacheMap<u32, 8> = SmallIntCacheMap::new();
+        let fills = Cell::new(0);
+
+        for _ in 0..3 {
+            let value = map
+                .get_or_insert_with(3, || {
+                    fills.set(fills.get() + 1);
+                    30
+                })
+                .unwrap();
+
+            assert_eq!(*value, 30);
+        }
+
+        assert_eq!(fills.get(), 1);
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.remove(3), Ok(Some(30)));
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn reports_out_of_range_keys() {
+        let map: SmallIntCacheMap<u32, 8> = SmallIntCacheMap::new();
+
+        let error = CacheError::KeyOutOfRange {
+            key: 8,
+            capacity: 8,
+        };
+
+        assert_eq!(map.insert(8, 1), Err(error));
+        assert!(map.get_or_insert_with(8, || 1).is_err());
+        assert_eq!(error.to_string(), "key 8 is outside a capacity of 8");
+    }
+}
@@ -0,0 +1,139 @@
+use super::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CacheSnapshot {
+    pub entries: BTreeMap<String, Value>,
+}
+
+impl CacheSnapshot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn to_json_string(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    pub fn from_json_str(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+struct RegisteredCache<'a> {
+    capture: Box<dyn Fn() -> Option<Result<Value, serde_json::Error>> + 'a>,
+    seed: Box<dyn Fn(Value) -> Result<(), serde_json::Error> + 'a>,
+}
+
+#[derive(Default)]
+pub struct SnapshotRegistry<'a> {
+    caches: BTreeMap<String, RegisteredCache<'a>>,
+}
+
+impl<'a> SnapshotRegistry<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<C, T>(&mut self, name: &str, cache: C)
+    where
+        C: FastCacheBackend<T> + Copy + 'a,
+        T: Serialize + DeserializeOwned,
+    {
+        let registered = RegisteredCache {
+            capture: Box::new(move || {
+                cache.map_backend(|data| data.as_ref().map(serde_json::to_value))
+            }),
+            seed: Box::new(move |value| {
+                let value = serde_json::from_value(value)?;
+
+                cache.map_backend(|data| *data = Some(value));
+
+                Ok(())
+            }),
+        };
+
+        self.caches.insert(name.to_owned(), registered);
+    }
+
+    pub fn snapshot(&self) -> Result<CacheSnapshot, serde_json::Error> {
+        let mut snapshot = CacheSnapshot::new();
+
+        for (name, cache) in &self.caches {
+            if let Some(value) = (cache.capture)() {
+                snapshot.entries.insert(name.clone(), value?);
+            }
+        }
+
+        Ok(snapshot)
+    }
+
+    pub fn apply(&self, snapshot: &CacheSnapshot) -> Result<(), serde_json::Error> {
+        for (name, value) in &snapshot.entries {
+            if let Some(cache) = self.caches.get(name) {
+                (cache.seed)(value.clone())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn restores_registered_caches_from_a_saved_snapshot() {
+        let paths: RefCell<Option<Vec<u32>>> = RefCell::new(None);
+        let owner: Mutex<Option<String>> = Mutex::new(None);
+        let unfilled: RefCell<Option<u8>> = RefCell::new(None);
+
+        let mut registry = SnapshotRegistry::new();
+        registry.register("paths", &paths);
+        registry.register("owner", &owner);
+        registry.register("unfilled", &unfilled);
+
+        (&paths).access(|_| false, || vec![1, 2, 3]).take();
+        *owner.lock().unwrap() = Some("Azaril".into());
+
+        let saved = registry.snapshot().unwrap().to_json_string().unwrap();
+
+        paths.borrow_mut().take();
+        owner.lock().unwrap().take();
+
+        let snapshot = CacheSnapshot::from_json_str(&saved).unwrap();
+        assert_eq!(snapshot.entries.len(), 2);
+
+        registry.apply(&snapshot).unwrap();
+
+        assert_eq!(*paths.borrow(), Some(vec![1, 2, 3]));
+        assert_eq!(owner.lock().unwrap().as_deref(), Some("Azaril"));
+        assert!(unfilled.borrow().is_none());
+        assert_eq!(
+            *(&paths).access(|_| false, || unreachable!()).take(),
+            vec![1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn apply_reports_mismatched_values() {
+        let count: RefCell<Option<u32>> = RefCell::new(None);
+
+        let mut registry = SnapshotRegistry::new();
+        registry.register("count", &count);
+
+        let mut snapshot = CacheSnapshot::new();
+        snapshot
+            .entries
+            .insert("count".into(), Value::from("not a number"));
+        snapshot.entries.insert("unknown".into(), Value::from(1));
+
+        assert!(registry.apply(&snapshot).is_err());
+        assert!(count.borrow().is_none());
+    }
+}
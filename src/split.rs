@@ -0,0 +1,76 @@
+use super::*;
+
+// Disjoint field borrows such as `(&mut self.cache).split_borrow(&self.rooms, ..)`
+// are accepted by the borrow checker, whereas a filler closure reading
+// `self.rooms` would capture all of `self` and conflict with the cache borrow.
+pub trait FastCacheSplitBorrowAccessor<'a, T, R>: FastCacheAccessor<'a, T, R> {
+    #[allow(clippy::type_complexity)]
+    fn split_borrow<'d, D, X, F>(
+        self,
+        sibling: &'d D,
+        expiration: X,
+        filler: F,
+    ) -> CacheAccesor<'a, T, Self, X, impl FnOnce() -> T + 'd, R>
+    where
+        D: ?Sized,
+        X: FnOnce(&T) -> bool,
+        F: FnOnce(&D) -> T + 'd;
+}
+
+impl<'a, C, T, R> FastCacheSplitBorrowAccessor<'a, T, R> for C
+where
+    C: FastCacheAccessor<'a, T, R>,
+{
+    #[allow(clippy::type_complexity)]
+    fn split_borrow<'d, D, X, F>(
+        self,
+        sibling: &'d D,
+        expiration: X,
+        filler: F,
+    ) -> CacheAccesor<'a, T, Self, X, impl FnOnce() -> T + 'd, R>
+    where
+        D: ?Sized,
+        X: FnOnce(&T) -> bool,
+        F: FnOnce(&D) -> T + 'd,
+    {
+        self.access(expiration, move || filler(sibling))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Colony {
+        rooms: Vec<String>,
+        total_name_length: Option<usize>,
+    }
+
+    impl Colony {
+        fn total_name_length(&mut self) -> usize {
+            *(&mut self.total_name_length)
+                .split_borrow(
+                    self.rooms.as_slice(),
+                    |_| false,
+                    |rooms| rooms.iter().map(String::len).sum(),
+                )
+                .take()
+        }
+    }
+
+    #[test]
+    fn caches_a_field_derived_from_a_sibling() {
+        let mut colony = Colony {
+            rooms: vec!["W1N1".into(), "E12S3".into()],
+            total_name_length: None,
+        };
+
+        assert_eq!(colony.total_name_length(), 9);
+
+        colony.rooms.push("W7N7".into());
+        assert_eq!(colony.total_name_length(), 9);
+
+        colony.total_name_length = None;
+        assert_eq!(colony.total_name_length(), 13);
+    }
+}
@@ -0,0 +1,197 @@
+use super::*;
+
+pub trait FastCachePeekExpiration<T> {
+    fn is_expired_by<X>(&self, expiration: X) -> bool
+    where
+        X: FnOnce(&T) -> bool;
+}
+
+impl<T> FastCachePeekExpiration<T> for &mut Option<T> {
+    fn is_expired_by<X>(&self, expiration: X) -> bool
+    where
+        X: FnOnce(&T) -> bool,
+    {
+        self.as_ref().map(expiration).unwrap_or(false)
+    }
+}
+
+impl<T> FastCachePeekExpiration<T> for &RefCell<Option<T>> {
+    fn is_expired_by<X>(&self, expiration: X) -> bool
+    where
+        X: FnOnce(&T) -> bool,
+    {
+        self.try_borrow()
+            .map(|v| v.as_ref().map(expiration).unwrap_or(false))
+            .unwrap_or(false)
+    }
+}
+
+impl<T> FastCachePeekExpiration<T> for &mut OwnedCache<T> {
+    fn is_expired_by<X>(&self, expiration: X) -> bool
+    where
+        X: FnOnce(&T) -> bool,
+    {
+        self.as_option().map(expiration).unwrap_or(false)
+    }
+}
+
+pub trait FastCachePeek<T> {
+    fn with_stored<U, P>(&self, peek: P) -> Option<U>
+    where
+        P: FnOnce(&T) -> U;
+}
+
+impl<T> FastCachePeek<T> for &mut Option<T> {
+    fn with_stored<U, P>(&self, peek: P) -> Option<U>
+    where
+        P: FnOnce(&T) -> U,
+    {
+        self.as_ref().map(peek)
+    }
+}
+
+impl<T> FastCachePeek<T> for &RefCell<Option<T>> {
+    fn with_stored<U, P>(&self, peek: P) -> Option<U>
+    where
+        P: FnOnce(&T) -> U,
+    {
+        self.try_borrow().ok()?.as_ref().map(peek)
+    }
+}
+
+impl<T> FastCachePeek<T> for &mut OwnedCache<T> {
+    fn with_stored<U, P>(&self, peek: P) -> Option<U>
+    where
+        P: FnOnce(&T) -> U,
+    {
+        self.as_option().map(peek)
+    }
+}
+
+pub trait FastCachePeekableAccessor<'a, T, R>:
+    FastCacheAccessor<'a, T, R> + FastCachePeekExpiration<T>
+{
+    fn access_peekable<X, F>(self, expiration: X, filler: F) -> CacheAccesor<'a, T, Self, X, F, R>
+    where
+        F: FnOnce() -> T,
+        X: Fn(&T) -> bool;
+}
+
+pub trait FastCacheMaybePeekableAccessor<'a, T, R>:
+    FastCacheMaybeAccessor<'a, T, R> + FastCachePeekExpiration<T>
+{
+    fn maybe_access_peekable<X, F>(
+        self,
+        expiration: X,
+        filler: F,
+    ) -> MaybeCacheAccesor<'a, T, Self, X, F, R>
+    where
+        F: FnOnce() -> Option<T>,
+        X: Fn(&T) -> bool;
+}
+
+impl<'a, C, T, R> FastCachePeekableAccessor<'a, T, R> for C
+where
+    C: FastCacheAccessor<'a, T, R> + FastCachePeekExpiration<T>,
+{
+    fn access_peekable<X, F>(self, expiration: X, filler: F) -> CacheAccesor<'a, T, Self, X, F, R>
+    where
+        F: FnOnce() -> T,
+        X: Fn(&T) -> bool,
+    {
+        self.access(expiration, filler)
+    }
+}
+
+impl<'a, C, T, R> FastCacheMaybePeekableAccessor<'a, T, R> for C
+where
+    C: FastCacheMaybeAccessor<'a, T, R> + FastCachePeekExpiration<T>,
+{
+    fn maybe_access_peekable<X, F>(
+        self,
+        expiration: X,
+        filler: F,
+    ) -> MaybeCacheAccesor<'a, T, Self, X, F, R>
+    where
+        F: FnOnce() -> Option<T>,
+        X: Fn(&T) -> bool,
+    {
+        self.maybe_access(expiration, filler)
+    }
+}
+
+impl<'c, T, C, X, F, R> CacheAccesor<'c, T, C, X, F, R>
+where
+    F: FnOnce() -> T,
+    X: Fn(&T) -> bool,
+    C: FastCacheGet<'c, T, R> + FastCacheExpiration<T> + FastCachePeekExpiration<T>,
+{
+    pub fn is_expired_now(&self) -> bool {
+        match &self.state {
+            CacheState::Unknown(state, _) => state.cache.is_expired_by(&state.expiration),
+            CacheState::Known(_) => false,
+        }
+    }
+}
+
+impl<'c, T, C, X, F, R> MaybeCacheAccesor<'c, T, C, X, F, R>
+where
+    F: FnOnce() -> Option<T>,
+    X: Fn(&T) -> bool,
+    C: FastCacheMaybeRetain<'c, T, R> + FastCacheExpiration<T> + FastCachePeekExpiration<T>,
+{
+    pub fn is_expired_now(&self) -> bool {
+        match &self.state {
+            MaybeCacheState::Unknown(state, _) => state.cache.is_expired_by(&state.expiration),
+            MaybeCacheState::Known(_) | MaybeCacheState::Empty(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_expired_now_reports_without_clearing() {
+        let mut cache = Some(3);
+
+        assert!(!(&mut cache)
+            .access_peekable(|v| *v > 5, || 9)
+            .is_expired_now());
+
+        let accessor = (&mut cache).access_peekable(|v| *v == 3, || 9);
+        assert!(accessor.is_expired_now());
+        assert!(accessor.is_expired_now());
+
+        assert_eq!(cache, Some(3));
+    }
+
+    #[test]
+    fn is_expired_now_is_false_once_resolved_or_empty() {
+        let cache = RefCell::new(None);
+
+        let mut accessor = (&cache).access_peekable(|_| true, || 1);
+        assert!(!accessor.is_expired_now());
+
+        accessor.get();
+        assert!(!accessor.is_expired_now());
+        drop(accessor);
+
+        let accessor = (&cache).maybe_access_peekable(|_| true, || None);
+        assert!(accessor.is_expired_now());
+        drop(accessor);
+
+        assert_eq!(*cache.borrow(), Some(1));
+    }
+
+    #[test]
+    fn with_stored_skips_a_mutably_borrowed_refcell() {
+        let cache = RefCell::new(Some(2));
+
+        assert_eq!((&cache).with_stored(|v| v * 2), Some(4));
+
+        let _guard = cache.borrow_mut();
+        assert_eq!((&cache).with_stored(|v| v * 2), None);
+    }
+}
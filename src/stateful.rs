@@ -0,0 +1,79 @@
+pub struct StatefulExpiration<'s, S, P> {
+    state: &'s mut S,
+    policy: P,
+}
+
+impl<'s, S, P> StatefulExpiration<'s, S, P> {
+    pub fn new<T>(state: &'s mut S, policy: P) -> Self
+    where
+        P: Fn(&mut S, &T) -> bool,
+    {
+        StatefulExpiration { state, policy }
+    }
+
+    pub fn state(&self) -> &S {
+        self.state
+    }
+
+    pub fn expiration<'e, T>(&'e mut self) -> impl FnOnce(&T) -> bool + 'e
+    where
+        P: Fn(&mut S, &T) -> bool,
+    {
+        let state: &'e mut S = self.state;
+        let policy = &self.policy;
+
+        move |v: &T| policy(state, v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    struct Backoff {
+        threshold: u32,
+        refreshes: u32,
+    }
+
+    #[test]
+    fn policy_state_persists_across_refreshes() {
+        let mut backoff = Backoff {
+            threshold: 1,
+            refreshes: 0,
+        };
+        let mut policy =
+            StatefulExpiration::new(&mut backoff, |s: &mut Backoff, v: &(u32, u32)| {
+                let (tick, filled_at) = *v;
+                let expired = tick - filled_at >= s.threshold;
+
+                if expired {
+                    s.refreshes += 1;
+                    s.threshold *= 2;
+                }
+
+                expired
+            });
+
+        let mut cache: Option<(u32, u32)> = None;
+        let mut fill_ticks = Vec::new();
+
+        for tick in 0..12 {
+            if let Some(v) = cache.as_mut() {
+                v.0 = tick;
+            }
+
+            let value = *(&mut cache)
+                .access(policy.expiration(), || (tick, tick))
+                .take();
+
+            if value.1 == tick {
+                fill_ticks.push(tick);
+            }
+        }
+
+        assert_eq!(fill_ticks, vec![0, 1, 3, 7]);
+        assert_eq!(policy.state().refreshes, 3);
+        assert_eq!(policy.state().threshold, 8);
+    }
+}
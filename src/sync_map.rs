@@ -0,0 +1,168 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::*;
+use std::sync::{Arc, OnceLock, RwLock};
+
+pub struct SyncCacheRef<V> {
+    cell: Arc<OnceLock<V>>,
+}
+
+impl<V> SyncCacheRef<V> {
+    pub(crate) fn from_cell(cell: Arc<OnceLock<V>>) -> Self {
+        SyncCacheRef { cell }
+    }
+}
+
+impl<V> Clone for SyncCacheRef<V> {
+    fn clone(&self) -> Self {
+        SyncCacheRef {
+            cell: self.cell.clone(),
+        }
+    }
+}
+
+impl<V> Deref for SyncCacheRef<V> {
+    type Target = V;
+
+    fn deref(&self) -> &V {
+        self.cell.get().unwrap()
+    }
+}
+
+pub struct SyncCacheMap<K, V> {
+    entries: RwLock<HashMap<K, Arc<OnceLock<V>>>>,
+}
+
+impl<K, V> Default for SyncCacheMap<K, V> {
+    fn default() -> Self {
+        SyncCacheMap {
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl<K, V> SyncCacheMap<K, V>
+where
+    K: Hash + Eq,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.read().unwrap().is_empty()
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<SyncCacheRef<V>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let entries = self.entries.read().unwrap();
+
+        let cell = entries.get(key)?;
+
+        cell.get()?;
+
+        Some(SyncCacheRef::from_cell(cell.clone()))
+    }
+
+    pub fn insert(&self, key: K, value: V) {
+        self.entries
+            .write()
+            .unwrap()
+            .insert(key, Arc::new(OnceLock::from(value)));
+    }
+
+    pub fn remove<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.entries.write().unwrap().remove(key).is_some()
+    }
+
+    pub fn clear(&self) {
+        self.entries.write().unwrap().clear();
+    }
+
+    pub fn get_or_insert_with<F>(&self, key: K, filler: F) -> SyncCacheRef<V>
+    where
+        F: FnOnce() -> V,
+    {
+        let existing = self.entries.read().unwrap().get(&key).cloned();
+
+        let cell = match existing {
+            Some(cell) => cell,
+            None => self
+                .entries
+                .write()
+                .unwrap()
+                .entry(key)
+                .or_default()
+                .clone(),
+        };
+
+        cell.get_or_init(filler);
+
+        SyncCacheRef::from_cell(cell)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Barrier;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn filler_runs_once_per_key_under_contention() {
+        let map = SyncCacheMap::new();
+        let fills = [AtomicUsize::new(0), AtomicUsize::new(0)];
+        let barrier = Barrier::new(8);
+
+        thread::scope(|scope| {
+            for i in 0..8 {
+                let (map, fills, barrier) = (&map, &fills, &barrier);
+
+                scope.spawn(move || {
+                    let key = i % 2;
+
+                    barrier.wait();
+
+                    let value = map.get_or_insert_with(key, || {
+                        fills[key].fetch_add(1, Ordering::SeqCst);
+                        thread::sleep(Duration::from_millis(20));
+                        key * 10
+                    });
+
+                    assert_eq!(*value, key * 10);
+                });
+            }
+        });
+
+        assert_eq!(fills[0].load(Ordering::SeqCst), 1);
+        assert_eq!(fills[1].load(Ordering::SeqCst), 1);
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn get_ignores_keys_without_a_value() {
+        let map = SyncCacheMap::new();
+
+        assert!(map.get(&1).is_none());
+
+        map.insert(1, "one");
+        assert_eq!(*map.get(&1).unwrap(), "one");
+
+        assert!(map.remove(&1));
+        assert!(map.get(&1).is_none());
+    }
+}
@@ -0,0 +1,614 @@
+use super::*;
+
+pub struct TickStamped<T> {
+    pub value: T,
+    pub tick: u32,
+}
+
+impl<T> TickStamped<T> {
+    pub fn new(value: T, tick: u32) -> Self {
+        TickStamped { value, tick }
+    }
+
+    pub fn age(&self, current_tick: u32) -> u32 {
+        current_tick.wrapping_sub(self.tick)
+    }
+
+    pub fn is_older_than(&self, current_tick: u32, ttl: u32) -> bool {
+        self.age(current_tick) >= ttl
+    }
+
+    pub fn ttl_remaining(&self, current_tick: u32, ttl: u32) -> u32 {
+        ttl.saturating_sub(self.age(current_tick))
+    }
+
+    pub fn debug_assert_fresh(&self, current_tick: u32) {
+        debug_assert_eq!(
+            self.tick, current_tick,
+            "cached value was filled on tick {} but is being used on tick {}",
+            self.tick, current_tick
+        );
+    }
+}
+
+impl<'c, T, C, X, F, R> CacheAccesor<'c, TickStamped<T>, C, X, F, R>
+where
+    F: FnOnce() -> TickStamped<T>,
+    X: FnOnce(&TickStamped<T>) -> bool,
+    C: FastCacheGet<'c, TickStamped<T>, R> + FastCacheExpiration<TickStamped<T>>,
+    R: Deref<Target = TickStamped<T>>,
+{
+    pub fn debug_assert_fresh(&self, current_tick: u32)
+    where
+        C: FastCachePeek<TickStamped<T>>,
+    {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+
+        match &self.state {
+            CacheState::Unknown(state, _) => {
+                state
+                    .cache
+                    .with_stored(|v: &TickStamped<T>| v.debug_assert_fresh(current_tick));
+            }
+            CacheState::Known(s) => s.data.debug_assert_fresh(current_tick),
+        }
+    }
+}
+
+pub trait FastCacheTickAccessor<'a, T, R>: FastCacheAccessor<'a, TickStamped<T>, R> {
+    #[allow(clippy::type_complexity)]
+    fn access_at_tick<F>(
+        self,
+        current_tick: u32,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        TickStamped<T>,
+        Self,
+        impl FnOnce(&TickStamped<T>) -> bool,
+        impl FnOnce() -> TickStamped<T>,
+        R,
+    >
+    where
+        F: FnOnce() -> T;
+}
+
+impl<'a, C, T, R> FastCacheTickAccessor<'a, T, R> for C
+where
+    C: FastCacheAccessor<'a, TickStamped<T>, R>,
+{
+    #[allow(clippy::type_complexity)]
+    fn access_at_tick<F>(
+        self,
+        current_tick: u32,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        TickStamped<T>,
+        Self,
+        impl FnOnce(&TickStamped<T>) -> bool,
+        impl FnOnce() -> TickStamped<T>,
+        R,
+    >
+    where
+        F: FnOnce() -> T,
+    {
+        self.access(
+            move |v: &TickStamped<T>| v.tick != current_tick,
+            move || TickStamped::new(filler(), current_tick),
+        )
+    }
+}
+
+pub struct ValidUntil<T> {
+    pub value: T,
+    pub valid_until: u32,
+}
+
+impl<T> ValidUntil<T> {
+    pub fn new(value: T, valid_until: u32) -> Self {
+        ValidUntil { value, valid_until }
+    }
+
+    pub fn is_valid_at(&self, current_tick: u32) -> bool {
+        current_tick < self.valid_until
+    }
+
+    pub fn ttl_remaining(&self, current_tick: u32) -> u32 {
+        self.valid_until.saturating_sub(current_tick)
+    }
+}
+
+impl<'c, T, C, X, F, R> CacheAccesor<'c, ValidUntil<T>, C, X, F, R>
+where
+    F: FnOnce() -> ValidUntil<T>,
+    X: FnOnce(&ValidUntil<T>) -> bool,
+    C: FastCacheGet<'c, ValidUntil<T>, R>
+        + FastCacheExpiration<ValidUntil<T>>
+        + FastCachePeek<ValidUntil<T>>,
+    R: Deref<Target = ValidUntil<T>>,
+{
+    pub fn ttl_remaining(&self, current_tick: u32) -> Option<u32> {
+        match &self.state {
+            CacheState::Unknown(state, _) => state
+                .cache
+                .with_stored(|v: &ValidUntil<T>| v.ttl_remaining(current_tick)),
+            CacheState::Known(s) => Some(s.data.ttl_remaining(current_tick)),
+        }
+    }
+}
+
+impl<'c, T, C, X, F, R> CacheAccesor<'c, TickStamped<T>, C, X, F, R>
+where
+    F: FnOnce() -> TickStamped<T>,
+    X: FnOnce(&TickStamped<T>) -> bool,
+    C: FastCacheGet<'c, TickStamped<T>, R>
+        + FastCacheExpiration<TickStamped<T>>
+        + FastCachePeek<TickStamped<T>>,
+    R: Deref<Target = TickStamped<T>>,
+{
+    pub fn ttl_remaining(&self, current_tick: u32, ttl: u32) -> Option<u32> {
+        match &self.state {
+            CacheState::Unknown(state, _) => state
+                .cache
+                .with_stored(|v: &TickStamped<T>| v.ttl_remaining(current_tick, ttl)),
+            CacheState::Known(s) => Some(s.data.ttl_remaining(current_tick, ttl)),
+        }
+    }
+}
+
+pub trait FastCacheValidRangeAccessor<'a, T, R>: FastCacheAccessor<'a, ValidUntil<T>, R> {
+    #[allow(clippy::type_complexity)]
+    fn access_valid_range<F>(
+        self,
+        current_tick: u32,
+        valid_until: u32,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        ValidUntil<T>,
+        Self,
+        impl FnOnce(&ValidUntil<T>) -> bool,
+        impl FnOnce() -> ValidUntil<T>,
+        R,
+    >
+    where
+        F: FnOnce() -> T;
+
+    #[allow(clippy::type_complexity)]
+    fn access_valid_range_with<F>(
+        self,
+        current_tick: u32,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        ValidUntil<T>,
+        Self,
+        impl FnOnce(&ValidUntil<T>) -> bool,
+        impl FnOnce() -> ValidUntil<T>,
+        R,
+    >
+    where
+        F: FnOnce() -> (T, u32);
+}
+
+impl<'a, C, T, R> FastCacheValidRangeAccessor<'a, T, R> for C
+where
+    C: FastCacheAccessor<'a, ValidUntil<T>, R>,
+{
+    #[allow(clippy::type_complexity)]
+    fn access_valid_range<F>(
+        self,
+        current_tick: u32,
+        valid_until: u32,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        ValidUntil<T>,
+        Self,
+        impl FnOnce(&ValidUntil<T>) -> bool,
+        impl FnOnce() -> ValidUntil<T>,
+        R,
+    >
+    where
+        F: FnOnce() -> T,
+    {
+        self.access(
+            move |v: &ValidUntil<T>| !v.is_valid_at(current_tick),
+            move || ValidUntil::new(filler(), valid_until),
+        )
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn access_valid_range_with<F>(
+        self,
+        current_tick: u32,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        ValidUntil<T>,
+        Self,
+        impl FnOnce(&ValidUntil<T>) -> bool,
+        impl FnOnce() -> ValidUntil<T>,
+        R,
+    >
+    where
+        F: FnOnce() -> (T, u32),
+    {
+        self.access(
+            move |v: &ValidUntil<T>| !v.is_valid_at(current_tick),
+            move || {
+                let (value, valid_until) = filler();
+
+                ValidUntil::new(value, valid_until)
+            },
+        )
+    }
+}
+
+pub trait FastCacheThrottledAccessor<'a, T, R>: FastCacheAccessor<'a, TickStamped<T>, R> {
+    #[allow(clippy::type_complexity)]
+    fn access_throttled<X, F>(
+        self,
+        current_tick: u32,
+        min_recompute_interval: u32,
+        expiration: X,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        TickStamped<T>,
+        Self,
+        impl FnOnce(&TickStamped<T>) -> bool,
+        impl FnOnce() -> TickStamped<T>,
+        R,
+    >
+    where
+        X: FnOnce(&T) -> bool,
+        F: FnOnce() -> T;
+}
+
+impl<'a, C, T, R> FastCacheThrottledAccessor<'a, T, R> for C
+where
+    C: FastCacheAccessor<'a, TickStamped<T>, R>,
+{
+    #[allow(clippy::type_complexity)]
+    fn access_throttled<X, F>(
+        self,
+        current_tick: u32,
+        min_recompute_interval: u32,
+        expiration: X,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        TickStamped<T>,
+        Self,
+        impl FnOnce(&TickStamped<T>) -> bool,
+        impl FnOnce() -> TickStamped<T>,
+        R,
+    >
+    where
+        X: FnOnce(&T) -> bool,
+        F: FnOnce() -> T,
+    {
+        self.access(
+            move |v: &TickStamped<T>| {
+                v.is_older_than(current_tick, min_recompute_interval) && expiration(&v.value)
+            },
+            move || TickStamped::new(filler(), current_tick),
+        )
+    }
+}
+
+pub trait FastCacheGraceAccessor<'a, T, R>: FastCacheAccessor<'a, TickStamped<T>, R> {
+    #[allow(clippy::type_complexity)]
+    fn access_with_grace<B, F>(
+        self,
+        current_tick: u32,
+        ttl: u32,
+        grace: u32,
+        within_budget: B,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        TickStamped<T>,
+        Self,
+        impl FnOnce(&TickStamped<T>) -> bool,
+        impl FnOnce() -> TickStamped<T>,
+        R,
+    >
+    where
+        B: FnOnce() -> bool,
+        F: FnOnce() -> T;
+}
+
+impl<'a, C, T, R> FastCacheGraceAccessor<'a, T, R> for C
+where
+    C: FastCacheAccessor<'a, TickStamped<T>, R>,
+{
+    #[allow(clippy::type_complexity)]
+    fn access_with_grace<B, F>(
+        self,
+        current_tick: u32,
+        ttl: u32,
+        grace: u32,
+        within_budget: B,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        TickStamped<T>,
+        Self,
+        impl FnOnce(&TickStamped<T>) -> bool,
+        impl FnOnce() -> TickStamped<T>,
+        R,
+    >
+    where
+        B: FnOnce() -> bool,
+        F: FnOnce() -> T,
+    {
+        self.access(
+            move |v: &TickStamped<T>| {
+                if !v.is_older_than(current_tick, ttl) {
+                    false
+                } else if v.is_older_than(current_tick, ttl.saturating_add(grace)) {
+                    true
+                } else {
+                    within_budget()
+                }
+            },
+            move || TickStamped::new(filler(), current_tick),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fresh_values_pass() {
+        let mut cache = None;
+
+        let mut accessor = (&mut cache).access_at_tick(5, || 1);
+
+        accessor.get();
+        accessor.debug_assert_fresh(5);
+    }
+
+    #[test]
+    fn empty_backends_pass() {
+        let mut cache: Option<TickStamped<u32>> = None;
+
+        (&mut cache).access_at_tick(5, || 1).debug_assert_fresh(5);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "filled on tick 4")]
+    fn stale_known_values_panic() {
+        let mut cache = Some(TickStamped::new(1, 4));
+
+        let mut accessor = (&mut cache).access(|_| false, || TickStamped::new(2, 5));
+
+        accessor.get();
+        accessor.debug_assert_fresh(5);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "filled on tick 4")]
+    fn stale_unresolved_values_panic() {
+        let cache = RefCell::new(Some(TickStamped::new(1, 4)));
+
+        (&cache).access_at_tick(5, || 2).debug_assert_fresh(5);
+    }
+
+    #[test]
+    fn valid_range_expires_at_the_boundary() {
+        let mut cache = None;
+
+        let at = |cache: &mut Option<ValidUntil<u32>>, tick: u32, value: u32| {
+            cache
+                .access_valid_range(tick, tick + 3, move || value)
+                .take()
+                .value
+        };
+
+        assert_eq!(at(&mut cache, 10, 1), 1);
+        assert_eq!(at(&mut cache, 12, 2), 1);
+        assert_eq!(at(&mut cache, 13, 3), 3);
+        assert_eq!(cache.as_ref().map(|v| v.valid_until), Some(16));
+    }
+
+    #[test]
+    fn filler_can_choose_its_window() {
+        let mut cache = None;
+
+        let value = (&mut cache)
+            .access_valid_range_with(0, || ("plan", 5))
+            .take()
+            .value;
+
+        assert_eq!(value, "plan");
+
+        let value = (&mut cache)
+            .access_valid_range_with(4, || ("replan", 9))
+            .take()
+            .value;
+
+        assert_eq!(value, "plan");
+
+        let value = (&mut cache)
+            .access_valid_range_with(5, || ("replan", 9))
+            .take()
+            .value;
+
+        assert_eq!(value, "replan");
+    }
+
+    #[test]
+    fn access_at_tick_is_valid_for_exactly_one_tick() {
+        let mut cache = None;
+        let fills = Cell::new(0);
+
+        let mut at = |tick: u32| {
+            (&mut cache)
+                .access_at_tick(tick, || {
+                    fills.set(fills.get() + 1);
+                    tick * 2
+                })
+                .take()
+                .value
+        };
+
+        assert_eq!(at(1), 2);
+        assert_eq!(at(1), 2);
+        assert_eq!(at(2), 4);
+        assert_eq!(at(2), 4);
+        assert_eq!(at(1), 2);
+        assert_eq!(fills.get(), 3);
+    }
+
+    #[test]
+    fn access_at_tick_handles_tick_wraparound() {
+        let cache = RefCell::new(None);
+
+        assert_eq!((&cache).access_at_tick(u32::MAX, || 1).take().value, 1);
+        assert_eq!((&cache).access_at_tick(0, || 2).take().value, 2);
+        assert_eq!(cache.borrow().as_ref().map(|v| v.tick), Some(0));
+    }
+
+    #[test]
+    fn access_throttled_recomputes_at_most_once_per_interval() {
+        let mut cache = None;
+        let fills = Cell::new(0);
+
+        let ticks: Vec<u32> = (0..10)
+            .map(|tick| {
+                (&mut cache)
+                    .access_throttled(
+                        tick,
+                        4,
+                        |_| true,
+                        || {
+                            fills.set(fills.get() + 1);
+                            tick
+                        },
+                    )
+                    .take()
+                    .value
+            })
+            .collect();
+
+        assert_eq!(ticks, vec![0, 0, 0, 0, 4, 4, 4, 4, 8, 8]);
+        assert_eq!(fills.get(), 3);
+    }
+
+    #[test]
+    fn access_throttled_still_respects_the_predicate() {
+        let mut cache = Some(TickStamped::new("old", 0));
+
+        let value = (&mut cache)
+            .access_throttled(20, 4, |_| false, || "new")
+            .take()
+            .value;
+
+        assert_eq!(value, "old");
+    }
+
+    fn graced(cache: &mut Option<TickStamped<u32>>, tick: u32, budget: bool) -> u32 {
+        cache
+            .access_with_grace(tick, 5, 3, || budget, move || tick)
+            .take()
+            .value
+    }
+
+    #[test]
+    fn grace_serves_stale_values_while_over_budget() {
+        let mut cache = None;
+
+        assert_eq!(graced(&mut cache, 0, false), 0);
+        assert_eq!(graced(&mut cache, 4, false), 0);
+        assert_eq!(graced(&mut cache, 5, false), 0);
+        assert_eq!(graced(&mut cache, 7, false), 0);
+        assert_eq!(graced(&mut cache, 8, false), 8);
+    }
+
+    #[test]
+    fn grace_refills_early_when_the_budget_allows() {
+        let mut cache = None;
+
+        assert_eq!(graced(&mut cache, 0, true), 0);
+        assert_eq!(graced(&mut cache, 4, true), 0);
+        assert_eq!(graced(&mut cache, 6, true), 6);
+    }
+
+    #[test]
+    fn ttl_remaining_counts_down_as_ticks_advance() {
+        let mut cache = None;
+
+        assert_eq!(
+            (&mut cache).access_at_tick(0, || 1).ttl_remaining(0, 5),
+            None
+        );
+
+        {
+            let mut accessor = (&mut cache).access_at_tick(0, || 1);
+            accessor.get();
+            assert_eq!(accessor.ttl_remaining(0, 5), Some(5));
+        }
+
+        let remaining: Vec<Option<u32>> = (0..7)
+            .map(|tick| {
+                (&mut cache)
+                    .access(|_| false, || unreachable!())
+                    .ttl_remaining(tick, 5)
+            })
+            .collect();
+
+        assert_eq!(
+            remaining,
+            vec![
+                Some(5),
+                Some(4),
+                Some(3),
+                Some(2),
+                Some(1),
+                Some(0),
+                Some(0)
+            ]
+        );
+    }
+
+    #[test]
+    fn valid_range_ttl_remaining_handles_empty_and_expired() {
+        let cache = RefCell::new(None);
+
+        assert_eq!(
+            (&cache).access_valid_range(0, 10, || 1).ttl_remaining(0),
+            None
+        );
+
+        *cache.borrow_mut() = Some(ValidUntil::new(1, 10));
+        assert_eq!(
+            (&cache).access_valid_range(0, 10, || 1).ttl_remaining(4),
+            Some(6)
+        );
+        assert_eq!(
+            (&cache).access_valid_range(0, 10, || 1).ttl_remaining(7),
+            Some(3)
+        );
+
+        assert_eq!(
+            (&cache).access_valid_range(0, 10, || 1).ttl_remaining(12),
+            Some(0)
+        );
+
+        *cache.borrow_mut() = Some(ValidUntil::new(1, u32::MAX));
+        assert_eq!(
+            (&cache).access_valid_range(0, 10, || 1).ttl_remaining(4),
+            Some(u32::MAX - 4)
+        );
+    }
+}
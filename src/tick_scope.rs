@@ -0,0 +1,114 @@
+use super::*;
+use std::rc::{Rc, Weak};
+
+thread_local! {
+    static TICK_EPOCH: Cell<u32> = const { Cell::new(0) };
+    static REGISTERED: RefCell<Vec<Box<dyn Fn() -> bool>>> = const { RefCell::new(Vec::new()) };
+}
+
+pub struct TickScope {
+    epoch: u32,
+}
+
+impl TickScope {
+    pub fn begin() -> Self {
+        let epoch = TICK_EPOCH.with(|e| {
+            let epoch = e.get().wrapping_add(1);
+
+            e.set(epoch);
+
+            epoch
+        });
+
+        // Expiring a cache may run arbitrary drop code, so the registry is
+        // taken out while the callbacks run.
+        let mut registered = REGISTERED.with(|r| r.take());
+
+        registered.retain(|expire| expire());
+
+        REGISTERED.with(|r| {
+            let mut r = r.borrow_mut();
+
+            registered.append(&mut r);
+
+            *r = registered;
+        });
+
+        TickScope { epoch }
+    }
+
+    pub fn epoch(&self) -> u32 {
+        self.epoch
+    }
+
+    pub fn current_epoch() -> u32 {
+        TICK_EPOCH.with(|e| e.get())
+    }
+
+    pub fn register<T>(cache: &Rc<RefCell<Option<T>>>)
+    where
+        T: 'static,
+    {
+        let cache: Weak<RefCell<Option<T>>> = Rc::downgrade(cache);
+
+        let expire = move || match cache.upgrade() {
+            Some(cache) => {
+                cache.borrow_mut().take();
+
+                true
+            }
+            None => false,
+        };
+
+        REGISTERED.with(|r| r.borrow_mut().push(Box::new(expire)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registered_caches_expire_at_the_next_scope() {
+        let first = TickScope::begin();
+
+        let paths = Rc::new(RefCell::new(None));
+        let targets = Rc::new(RefCell::new(None));
+        TickScope::register(&paths);
+        TickScope::register(&targets);
+
+        assert_eq!(
+            *(&*paths).access(|_| false, || vec![1, 2]).take(),
+            vec![1, 2]
+        );
+        assert_eq!(*(&*targets).access(|_| false, || 7).take(), 7);
+
+        let second = TickScope::begin();
+        assert_eq!(second.epoch(), first.epoch().wrapping_add(1));
+        assert_eq!(TickScope::current_epoch(), second.epoch());
+
+        assert!(paths.borrow().is_none());
+        assert!(targets.borrow().is_none());
+
+        *targets.borrow_mut() = Some(8);
+        TickScope::begin();
+        assert!(targets.borrow().is_none());
+    }
+
+    #[test]
+    fn dropped_caches_are_unregistered() {
+        let kept = Rc::new(RefCell::new(Some(1)));
+        let dropped = Rc::new(RefCell::new(Some(2)));
+        TickScope::register(&kept);
+        TickScope::register(&dropped);
+
+        let weak = Rc::downgrade(&dropped);
+        drop(dropped);
+
+        TickScope::begin();
+
+        assert!(weak.upgrade().is_none());
+        assert!(kept.borrow().is_none());
+        assert_eq!(REGISTERED.with(|r| r.borrow().len()), 1);
+    }
+}
@@ -0,0 +1,138 @@
+use super::*;
+use std::time::Instant;
+use tracing::field;
+
+impl<'c, T, C, X, F, R> CacheAccesor<'c, T, C, X, F, R>
+where
+    F: FnOnce() -> T,
+    X: FnOnce(&T) -> bool,
+    C: FastCacheGet<'c, T, R> + FastCacheExpiration<T>,
+{
+    pub fn get_traced(&mut self) -> &R {
+        self.get_wrapping_fill(|refresh, fill| {
+            let span = tracing::span!(
+                tracing::Level::TRACE,
+                "cache_fill",
+                refresh,
+                duration_us = field::Empty
+            );
+            let _entered = span.enter();
+
+            let start = Instant::now();
+            let value = cold_fill(fill);
+
+            span.record("duration_us", start.elapsed().as_micros() as u64);
+
+            value
+        })
+        .0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    #[derive(Default)]
+    struct Recorded {
+        spans: Vec<(String, bool)>,
+        durations: usize,
+    }
+
+    struct Recorder {
+        recorded: Arc<Mutex<Recorded>>,
+        next_id: AtomicU64,
+    }
+
+    #[derive(Default)]
+    struct Fields {
+        refresh: Option<bool>,
+        duration_us: Option<u64>,
+    }
+
+    impl Visit for Fields {
+        fn record_bool(&mut self, field: &Field, value: bool) {
+            if field.name() == "refresh" {
+                self.refresh = Some(value);
+            }
+        }
+
+        fn record_u64(&mut self, field: &Field, value: u64) {
+            if field.name() == "duration_us" {
+                self.duration_us = Some(value);
+            }
+        }
+
+        fn record_debug(&mut self, _: &Field, _: &dyn std::fmt::Debug) {}
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut fields = Fields::default();
+            span.record(&mut fields);
+
+            self.recorded.lock().unwrap().spans.push((
+                span.metadata().name().to_string(),
+                fields
+                    .refresh
+                    .expect("cache_fill spans carry a refresh field"),
+            ));
+
+            Id::from_u64(self.next_id.fetch_add(1, Ordering::SeqCst))
+        }
+
+        fn record(&self, _: &Id, values: &Record<'_>) {
+            let mut fields = Fields::default();
+            values.record(&mut fields);
+
+            if fields.duration_us.is_some() {
+                self.recorded.lock().unwrap().durations += 1;
+            }
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, _: &Event<'_>) {}
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn fills_emit_spans_with_refresh_and_duration() {
+        let recorded = Arc::new(Mutex::new(Recorded::default()));
+        let recorder = Recorder {
+            recorded: recorded.clone(),
+            next_id: AtomicU64::new(1),
+        };
+
+        tracing::subscriber::with_default(recorder, || {
+            let mut cache = None;
+
+            assert_eq!(**(&mut cache).access(|_| false, || 1).get_traced(), 1);
+            assert_eq!(**(&mut cache).access(|_| false, || 2).get_traced(), 1);
+            assert_eq!(**(&mut cache).access(|_| true, || 3).get_traced(), 3);
+        });
+
+        let recorded = recorded.lock().unwrap();
+
+        assert_eq!(
+            recorded.spans,
+            vec![
+                ("cache_fill".to_string(), false),
+                ("cache_fill".to_string(), true)
+            ]
+        );
+        assert_eq!(recorded.durations, 2);
+    }
+}
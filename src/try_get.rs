@@ -0,0 +1,153 @@
+use super::*;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CacheError {
+    BorrowConflict,
+    Empty,
+    KeyOutOfRange { key: usize, capacity: usize },
+    OutOfRange,
+    TypeMismatch,
+    WeightOverflow,
+}
+
+impl fmt::Display for CacheError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CacheError::BorrowConflict => write!(f, "cache is already borrowed"),
+            CacheError::Empty => write!(f, "cache fill produced no value"),
+            CacheError::KeyOutOfRange { key, capacity } => {
+                write!(f, "key {} is outside a capacity of {}", key, capacity)
+            }
+            CacheError::OutOfRange => write!(f, "range is outside the cached value"),
+            CacheError::TypeMismatch => write!(f, "cached value has a different type"),
+            CacheError::WeightOverflow => write!(f, "total cache weight would overflow"),
+        }
+    }
+}
+
+impl Error for CacheError {}
+
+pub trait FastCacheBorrowCheck {
+    fn can_access(&self) -> bool;
+}
+
+impl<T> FastCacheBorrowCheck for &mut Option<T> {
+    fn can_access(&self) -> bool {
+        true
+    }
+}
+
+impl<T> FastCacheBorrowCheck for &RefCell<Option<T>> {
+    fn can_access(&self) -> bool {
+        // Resolving runs the expiration, which takes the value out through a
+        // mutable borrow when it fires. The expiration is consumed by that
+        // call, so it cannot be checked up front; hits therefore need the
+        // mutable borrow to be available just like fills do.
+        self.try_borrow_mut().is_ok()
+    }
+}
+
+impl<T> FastCacheBorrowCheck for &mut OwnedCache<T> {
+    fn can_access(&self) -> bool {
+        true
+    }
+}
+
+impl<'c, T, C, X, F, R> CacheAccesor<'c, T, C, X, F, R>
+where
+    F: FnOnce() -> T,
+    X: FnOnce(&T) -> bool,
+    C: FastCacheGet<'c, T, R> + FastCacheExpiration<T> + FastCacheBorrowCheck,
+{
+    pub fn try_get_ref(&mut self) -> Result<&R, CacheError> {
+        if let CacheState::Unknown(state, _) = &self.state {
+            if !state.cache.can_access() {
+                return Err(CacheError::BorrowConflict);
+            }
+        }
+
+        Ok(self.get())
+    }
+}
+
+impl<'c, T, C, X, F, R> MaybeCacheAccesor<'c, T, C, X, F, R>
+where
+    F: FnOnce() -> Option<T>,
+    X: FnOnce(&T) -> bool,
+    C: FastCacheMaybeRetain<'c, T, R> + FastCacheExpiration<T> + FastCacheBorrowCheck,
+{
+    pub fn try_get_ref(&mut self) -> Result<&R, CacheError> {
+        if let MaybeCacheState::Unknown(state, _) = &self.state {
+            if !state.cache.can_access() {
+                return Err(CacheError::BorrowConflict);
+            }
+        }
+
+        self.get().ok_or(CacheError::Empty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expiring_hits_report_conflicts_while_the_value_is_borrowed() {
+        let cache = RefCell::new(Some(1));
+
+        {
+            let held = cache.borrow();
+            let mut accessor = (&cache).access(|_| true, || 2);
+
+            assert!(matches!(
+                accessor.try_get_ref(),
+                Err(CacheError::BorrowConflict)
+            ));
+            assert_eq!(*held, Some(1));
+        }
+
+        let mut accessor = (&cache).access(|_| true, || 2);
+
+        assert_eq!(accessor.try_get_ref().map(|v| **v), Ok(2));
+    }
+
+    #[test]
+    fn fills_report_borrow_conflicts() {
+        let cache: RefCell<Option<u32>> = RefCell::new(None);
+        let held = cache.borrow();
+
+        let mut accessor = (&cache).access(|_| false, || 2);
+
+        assert!(matches!(
+            accessor.try_get_ref(),
+            Err(CacheError::BorrowConflict)
+        ));
+
+        drop(held);
+
+        assert_eq!(accessor.try_get_ref().map(|v| **v), Ok(2));
+    }
+
+    #[test]
+    fn mutable_borrows_report_conflicts_on_hits() {
+        let cache = RefCell::new(Some(1));
+        let held = cache.borrow_mut();
+
+        let mut accessor = (&cache).access(|_| false, || 2);
+
+        assert!(accessor.try_get_ref().is_err());
+
+        drop(held);
+    }
+
+    #[test]
+    fn empty_fills_report_empty() {
+        let mut cache: Option<u32> = None;
+
+        let mut accessor = (&mut cache).maybe_access(|_| false, || None);
+
+        assert!(matches!(accessor.try_get_ref(), Err(CacheError::Empty)));
+    }
+}
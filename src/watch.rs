@@ -0,0 +1,107 @@
+use super::*;
+
+pub struct MultiWatch<T, W> {
+    pub value: T,
+    pub watched: W,
+}
+
+impl<T, W> MultiWatch<T, W>
+where
+    W: PartialEq,
+{
+    pub fn new(value: T, watched: W) -> Self {
+        MultiWatch { value, watched }
+    }
+
+    pub fn has_changed(&self, current: &W) -> bool {
+        self.watched != *current
+    }
+}
+
+pub trait FastCacheMultiWatchAccessor<'a, T, W, R>:
+    FastCacheAccessor<'a, MultiWatch<T, W>, R>
+{
+    #[allow(clippy::type_complexity)]
+    fn access_watching<F>(
+        self,
+        watched: W,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        MultiWatch<T, W>,
+        Self,
+        impl FnOnce(&MultiWatch<T, W>) -> bool,
+        impl FnOnce() -> MultiWatch<T, W>,
+        R,
+    >
+    where
+        W: PartialEq + Clone,
+        F: FnOnce() -> T;
+}
+
+impl<'a, C, T, W, R> FastCacheMultiWatchAccessor<'a, T, W, R> for C
+where
+    C: FastCacheAccessor<'a, MultiWatch<T, W>, R>,
+{
+    #[allow(clippy::type_complexity)]
+    fn access_watching<F>(
+        self,
+        watched: W,
+        filler: F,
+    ) -> CacheAccesor<
+        'a,
+        MultiWatch<T, W>,
+        Self,
+        impl FnOnce(&MultiWatch<T, W>) -> bool,
+        impl FnOnce() -> MultiWatch<T, W>,
+        R,
+    >
+    where
+        W: PartialEq + Clone,
+        F: FnOnce() -> T,
+    {
+        let stored = watched.clone();
+
+        self.access(
+            move |v: &MultiWatch<T, W>| v.has_changed(&watched),
+            move || MultiWatch::new(filler(), stored),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refills_when_any_watched_input_changes() {
+        let mut cache = None;
+        let fills = Cell::new(0);
+
+        let mut fetch = |energy: u32, level: u8, hostile: bool| {
+            (&mut cache)
+                .access_watching((energy, level, hostile), || {
+                    fills.set(fills.get() + 1);
+                    fills.get()
+                })
+                .take()
+                .value
+        };
+
+        assert_eq!(fetch(100, 3, false), 1);
+        assert_eq!(fetch(100, 3, false), 1);
+        assert_eq!(fetch(100, 4, false), 2);
+        assert_eq!(fetch(100, 4, true), 3);
+        assert_eq!(fetch(100, 4, true), 3);
+        assert_eq!(fetch(50, 4, true), 4);
+    }
+
+    #[test]
+    fn watches_a_vec_of_inputs() {
+        let stored = MultiWatch::new("plan", vec![1, 2, 3]);
+
+        assert!(!stored.has_changed(&vec![1, 2, 3]));
+        assert!(stored.has_changed(&vec![1, 2, 4]));
+        assert!(stored.has_changed(&vec![1, 2]));
+    }
+}